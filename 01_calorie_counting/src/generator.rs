@@ -0,0 +1,67 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{generator::Generator, random::Rng};
+
+pub struct ElfSnacks {
+    pub elves: usize,
+    pub snacks_per_elf: RangeInclusive<usize>,
+    pub calories_per_snack: RangeInclusive<u32>,
+}
+
+impl Default for ElfSnacks {
+    fn default() -> Self {
+        Self {
+            elves: 250,
+            snacks_per_elf: 1..=15,
+            calories_per_snack: 1000..=60000,
+        }
+    }
+}
+
+impl Generator for ElfSnacks {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            elves: default.elves * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.elves)
+            .map(|_| {
+                (0..rng.range(self.snacks_per_elf.clone()))
+                    .map(|_| rng.range(self.calories_per_snack.clone()).to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::CalorieCounting;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        for seed in 0..10 {
+            let input = ElfSnacks::default().generate(&mut Rng::new(seed));
+            let calorie_counts = CalorieCounting::parse(&input).unwrap();
+
+            assert_eq!(calorie_counts.len(), 250);
+            assert!(CalorieCounting::part_two(&calorie_counts).is_ok());
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<CalorieCounting>(1, 200).unwrap();
+    }
+}
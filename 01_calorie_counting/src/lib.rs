@@ -0,0 +1,676 @@
+pub mod generator;
+pub mod rebalance;
+pub mod report;
+pub mod statistics;
+
+use std::{fmt, io::BufRead, num::IntErrorKind};
+
+use aoc_common::{
+    config::{self, integer_at_least, unknown_key},
+    diagnostic,
+    input::{lines, paragraphs},
+    stream::{locate_on_line, LineReader, StreamingSolution},
+    toml::Value,
+    top::{top_k, TopK},
+    Result, Solution,
+};
+
+/// The calories of a single snack.
+pub type Calories = u64;
+
+/// The calories of several snacks added up, as wide as any `CalorieWidth` allows.
+pub type CalorieCount = u128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCalories,
+    NegativeCalories,
+    TooManyCalories,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCalories => write!(f, "Calories should be an integer"),
+            Self::NegativeCalories => write!(f, "Calories can't be negative"),
+            Self::TooManyCalories => {
+                write!(f, "A snack can't have more than {} calories", Calories::MAX)
+            }
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+fn parse_calories(calories: &str) -> Result<Calories, ParseErrorKind> {
+    calories
+        .parse::<Calories>()
+        .map_err(|error| match error.kind() {
+            IntErrorKind::PosOverflow => ParseErrorKind::TooManyCalories,
+            _ if calories.strip_prefix('-').is_some_and(|magnitude| {
+                !magnitude.is_empty() && magnitude.bytes().all(|byte| byte.is_ascii_digit())
+            }) =>
+            {
+                ParseErrorKind::NegativeCalories
+            }
+            _ => ParseErrorKind::InvalidCalories,
+        })
+}
+
+/// How many bits calorie counts are allowed to take, past which adding them up is an error
+/// rather than wrapping around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalorieWidth {
+    Bits32,
+    Bits64,
+    Bits128,
+}
+
+impl CalorieWidth {
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::Bits32 => 32,
+            Self::Bits64 => 64,
+            Self::Bits128 => 128,
+        }
+    }
+
+    pub fn max(self) -> CalorieCount {
+        CalorieCount::MAX >> (CalorieCount::BITS - self.bits())
+    }
+
+    pub fn checked_add(self, augend: CalorieCount, addend: CalorieCount) -> Option<CalorieCount> {
+        augend.checked_add(addend).filter(|&sum| sum <= self.max())
+    }
+}
+
+fn elf_overflow_error(position: usize, width: CalorieWidth) -> aoc_common::Error {
+    format!(
+        "The calories of elf {} add up to more than {} bits can hold",
+        position,
+        width.bits()
+    )
+    .into()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// How many of the elves carrying the most calories the second part adds up.
+    pub top_elves: usize,
+    /// How wide calorie counts can get.
+    pub calorie_width: CalorieWidth,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            top_elves: 3,
+            calorie_width: CalorieWidth::Bits64,
+        }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &["top_elves", "calorie_bits"];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "top_elves" => self.top_elves = integer_at_least(key, value, 1)?,
+            "calorie_bits" => {
+                self.calorie_width = match integer_at_least::<u32>(key, value, 0)? {
+                    32 => CalorieWidth::Bits32,
+                    64 => CalorieWidth::Bits64,
+                    128 => CalorieWidth::Bits128,
+                    bits => {
+                        return Err(format!("{} should be 32, 64 or 128, not {}", key, bits).into())
+                    }
+                }
+            }
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf comes in the input, counting from 1.
+    pub position: usize,
+    /// The calories of each of the elf's snacks, in the order they are listed.
+    pub snacks: Vec<Calories>,
+}
+
+impl Elf {
+    /// The calories of all the elf's snacks. There can't be enough snacks in memory for this to
+    /// overflow, so it's only checked against a `CalorieWidth` by `checked_calorie_count`.
+    pub fn calorie_count(&self) -> CalorieCount {
+        self.snacks
+            .iter()
+            .map(|&calories| CalorieCount::from(calories))
+            .sum()
+    }
+
+    pub fn checked_calorie_count(&self, width: CalorieWidth) -> Result<CalorieCount> {
+        let calorie_count = self.calorie_count();
+
+        match calorie_count <= width.max() {
+            true => Ok(calorie_count),
+            false => Err(elf_overflow_error(self.position, width)),
+        }
+    }
+}
+
+/// The `count` elves carrying the most calories with their calorie counts, the most first. Tied
+/// elves are kept in the order they come in.
+pub fn top_elves(elves: &[Elf], count: usize) -> Vec<(&Elf, CalorieCount)> {
+    top_k(elves.iter().map(Elf::calorie_count), count)
+        .into_iter()
+        .map(|(elf_index, calorie_count)| (&elves[elf_index], calorie_count))
+        .collect()
+}
+
+/// Adds up the calorie counts of the `top_elves` elves carrying the most, making sure there are
+/// that many and that their sum fits in the configured width.
+fn combine_top_calorie_counts(
+    top_calorie_counts: &[CalorieCount],
+    config: &Config,
+) -> Result<CalorieCount> {
+    if top_calorie_counts.len() < config.top_elves {
+        return Err(format!(
+            "We should have at least {} elves for the second part of the puzzle",
+            config.top_elves
+        )
+        .into());
+    }
+
+    top_calorie_counts
+        .iter()
+        .try_fold(0, |sum, &calorie_count| {
+            config.calorie_width.checked_add(sum, calorie_count)
+        })
+        .ok_or_else(|| {
+            format!(
+                "The calories of the {} elves carrying the most add up to more than {} bits can \
+                 hold",
+                config.top_elves,
+                config.calorie_width.bits()
+            )
+            .into()
+        })
+}
+
+pub struct CalorieCounting;
+
+impl Solution for CalorieCounting {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "calorie_counting";
+
+    type Input = Vec<Elf>;
+    type PartOne = CalorieCount;
+    type PartTwo = CalorieCount;
+
+    type Generator = generator::ElfSnacks;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let elves = paragraphs(input)
+            .enumerate()
+            .map(|(elf_index, elf_calories)| {
+                let snacks = lines(elf_calories)
+                    .map(|calories| {
+                        parse_calories(calories)
+                            .map_err(|kind| ParseError::at(input, calories, kind).locate(input))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Elf {
+                    position: elf_index + 1,
+                    snacks,
+                })
+            });
+
+        elves.collect()
+    }
+
+    fn part_one_with(elves: &Self::Input, config: &Self::Config) -> Result<Self::PartOne> {
+        for elf in elves {
+            elf.checked_calorie_count(config.calorie_width)?;
+        }
+
+        let (_, biggest_calorie_count) = top_elves(elves, 1)
+            .pop()
+            .ok_or("Some elf should have the max calories")?;
+
+        Ok(biggest_calorie_count)
+    }
+
+    fn part_two_with(elves: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        for elf in elves {
+            elf.checked_calorie_count(config.calorie_width)?;
+        }
+
+        let top_calorie_counts = top_elves(elves, config.top_elves)
+            .into_iter()
+            .map(|(_, calorie_count)| calorie_count)
+            .collect::<Vec<_>>();
+
+        combine_top_calorie_counts(&top_calorie_counts, config)
+    }
+}
+
+impl StreamingSolution for CalorieCounting {
+    /// The `top_elves` biggest calorie counts, in descending order.
+    type Summary = Vec<CalorieCount>;
+
+    fn summarize_stream(reader: &mut dyn BufRead, config: &Self::Config) -> Result<Self::Summary> {
+        let mut lines = LineReader::new(reader);
+
+        let mut top_calorie_counts = TopK::new(config.top_elves);
+        let mut elf_calorie_count = None;
+        let mut elf_position = 1;
+        let mut blank_lines = 0;
+        let mut second_blank_line_number = 0;
+
+        while let Some((line_number, calories)) = lines.next_line()? {
+            if calories.is_empty() {
+                if let Some(calorie_count) = elf_calorie_count.take() {
+                    top_calorie_counts.push(calorie_count);
+                    elf_position += 1;
+                }
+
+                blank_lines += 1;
+
+                if blank_lines == 2 {
+                    second_blank_line_number = line_number;
+                }
+
+                continue;
+            }
+
+            // Like `parse`, which splits elves on a blank line, a third blank line in a row leaves
+            // an elf with a blank snack, unless it only pads the start of the input.
+            if blank_lines >= 3 && elf_position > 1 {
+                return Err(locate_on_line(
+                    ParseError::at("", "", ParseErrorKind::InvalidCalories),
+                    "",
+                    second_blank_line_number,
+                )
+                .into());
+            }
+
+            blank_lines = 0;
+
+            let calories = parse_calories(calories).map_err(|kind| {
+                locate_on_line(
+                    ParseError::at(calories, calories, kind),
+                    calories,
+                    line_number,
+                )
+            })?;
+
+            // Checked as it goes, since an elf's snacks aren't kept to add them up later.
+            elf_calorie_count = Some(
+                config
+                    .calorie_width
+                    .checked_add(elf_calorie_count.unwrap_or(0), calories.into())
+                    .ok_or_else(|| elf_overflow_error(elf_position, config.calorie_width))?,
+            );
+        }
+
+        if let Some(calorie_count) = elf_calorie_count {
+            top_calorie_counts.push(calorie_count);
+        }
+
+        Ok(top_calorie_counts
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(_, calorie_count)| calorie_count)
+            .collect())
+    }
+
+    fn part_one_from_summary(
+        top_calorie_counts: &Self::Summary,
+        _: &Self::Config,
+    ) -> Result<Self::PartOne> {
+        Ok(*top_calorie_counts
+            .first()
+            .ok_or("Some elf should have the max calories")?)
+    }
+
+    fn part_two_from_summary(
+        top_calorie_counts: &Self::Summary,
+        config: &Self::Config,
+    ) -> Result<Self::PartTwo> {
+        combine_top_calorie_counts(top_calorie_counts, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        diagnostic::LocatedError,
+        generator::Generator,
+        normalize::{normalize, variants, NormalizedReader},
+        random::Rng,
+    };
+
+    use crate::generator::ElfSnacks;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn parses_calorie_counts_in_elf_order() {
+        let elves = CalorieCounting::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            elves.iter().map(Elf::calorie_count).collect::<Vec<_>>(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+    }
+
+    #[test]
+    fn keeps_each_elf_position_and_snacks() {
+        let elves = CalorieCounting::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            elves[3],
+            Elf {
+                position: 4,
+                snacks: vec![7000, 8000, 9000]
+            }
+        );
+        assert_eq!(
+            top_elves(&elves, 2)
+                .into_iter()
+                .map(|(elf, calorie_count)| (elf.position, calorie_count))
+                .collect::<Vec<_>>(),
+            vec![(4, 24000), (3, 11000)]
+        );
+    }
+
+    #[test]
+    fn finds_the_top_percent_of_a_large_expedition() {
+        let input = ElfSnacks::scaled(40).generate(&mut Rng::new(1));
+        let calorie_counts = CalorieCounting::parse(&input)
+            .unwrap()
+            .iter()
+            .map(Elf::calorie_count)
+            .collect::<Vec<_>>();
+
+        let top_percent = top_k(
+            calorie_counts.iter().copied(),
+            calorie_counts.len().div_ceil(100),
+        );
+
+        assert_eq!(top_percent.len(), 100);
+
+        for &(elf_index, calorie_count) in top_percent.iter() {
+            assert_eq!(calorie_counts[elf_index], calorie_count);
+        }
+
+        let (_, smallest_top_count) = top_percent[top_percent.len() - 1];
+
+        assert_eq!(
+            calorie_counts
+                .iter()
+                .filter(|&&calorie_count| calorie_count > smallest_top_count)
+                .count(),
+            top_percent.len() - 1
+        );
+    }
+
+    #[test]
+    fn reports_invalid_calories_with_their_location() {
+        let error = CalorieCounting::parse("1000\n2000\n\n3x00").unwrap_err();
+        let error = error
+            .downcast_ref::<LocatedError<ParseErrorKind>>()
+            .unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCalories);
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.snippet, "3x00");
+    }
+
+    #[test]
+    fn tells_negative_and_oversized_calories_apart() {
+        let locate = |input: &str| {
+            CalorieCounting::parse(input)
+                .unwrap_err()
+                .downcast::<LocatedError<ParseErrorKind>>()
+                .unwrap()
+        };
+
+        let error = locate("1000\n-2000");
+
+        assert_eq!(error.kind, ParseErrorKind::NegativeCalories);
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = locate("1000\n\n18446744073709551616");
+
+        assert_eq!(error.kind, ParseErrorKind::TooManyCalories);
+        assert_eq!(error.line, 3);
+
+        assert_eq!(locate("1.5").kind, ParseErrorKind::InvalidCalories);
+        assert_eq!(locate("-").kind, ParseErrorKind::InvalidCalories);
+    }
+
+    #[test]
+    fn names_the_elf_whose_calories_overflow() {
+        let input = "1\n\n4294967295\n1\n\n2";
+        let elves = CalorieCounting::parse(input).unwrap();
+
+        let narrow = Config {
+            calorie_width: CalorieWidth::Bits32,
+            ..Config::default()
+        };
+        let message = "The calories of elf 2 add up to more than 32 bits can hold";
+
+        assert_eq!(
+            CalorieCounting::part_one_with(&elves, &narrow)
+                .unwrap_err()
+                .to_string(),
+            message
+        );
+        assert_eq!(
+            CalorieCounting::summarize_stream(&mut input.as_bytes(), &narrow)
+                .unwrap_err()
+                .to_string(),
+            message
+        );
+
+        assert_eq!(CalorieCounting::part_one(&elves).unwrap(), 4294967296);
+        assert_eq!(CalorieCounting::part_two(&elves).unwrap(), 4294967299);
+    }
+
+    #[test]
+    fn checks_the_combined_calories_of_the_top_elves() {
+        let elves = CalorieCounting::parse("4294967295\n\n1").unwrap();
+        let config = Config {
+            top_elves: 2,
+            calorie_width: CalorieWidth::Bits32,
+        };
+
+        assert!(CalorieCounting::part_one_with(&elves, &config).is_ok());
+        assert!(CalorieCounting::part_two_with(&elves, &config).is_err());
+
+        let summary =
+            CalorieCounting::summarize_stream(&mut "4294967295\n\n1".as_bytes(), &config).unwrap();
+
+        assert!(CalorieCounting::part_two_from_summary(&summary, &config).is_err());
+
+        let huge = format!("{}\n{}\n{}", u64::MAX, u64::MAX, u64::MAX);
+        let elves = CalorieCounting::parse(&huge).unwrap();
+        let config = Config {
+            top_elves: 1,
+            calorie_width: CalorieWidth::Bits128,
+        };
+
+        assert_eq!(
+            CalorieCounting::part_two_with(&elves, &config).unwrap(),
+            3 * CalorieCount::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn reads_the_calorie_width_in_bits() {
+        let mut config = Config::default();
+
+        config::Config::set(&mut config, "calorie_bits", &Value::Integer(128)).unwrap();
+
+        assert_eq!(config.calorie_width, CalorieWidth::Bits128);
+        assert_eq!(CalorieWidth::Bits32.max(), u32::MAX.into());
+        assert!(config::Config::set(&mut config, "calorie_bits", &Value::Integer(48)).is_err());
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = CalorieCounting::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                CalorieCounting::part_one(&input).unwrap(),
+                24000,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                CalorieCounting::part_two(&input).unwrap(),
+                45000,
+                "{}",
+                variant
+            );
+
+            let summary = CalorieCounting::summarize_stream(
+                &mut NormalizedReader::new(text.as_bytes()),
+                &Config::default(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                CalorieCounting::part_one_from_summary(&summary, &Config::default()).unwrap(),
+                24000,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                CalorieCounting::part_two_from_summary(&summary, &Config::default()).unwrap(),
+                45000,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = CalorieCounting::parse(EXAMPLE).unwrap();
+
+        assert_eq!(CalorieCounting::part_one(&input).unwrap(), 24000);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = CalorieCounting::parse(EXAMPLE).unwrap();
+
+        assert_eq!(CalorieCounting::part_two(&input).unwrap(), 45000);
+    }
+
+    #[test]
+    fn adds_up_as_many_top_elves_as_configured() {
+        let input = CalorieCounting::parse(EXAMPLE).unwrap();
+        let config = Config {
+            top_elves: 2,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            CalorieCounting::part_two_with(&input, &config).unwrap(),
+            35000
+        );
+
+        let summary = CalorieCounting::summarize_stream(&mut EXAMPLE.as_bytes(), &config).unwrap();
+
+        assert_eq!(summary, vec![24000, 11000]);
+    }
+
+    #[test]
+    fn streams_the_example_keeping_the_top_three() {
+        let summary =
+            CalorieCounting::summarize_stream(&mut EXAMPLE.as_bytes(), &Config::default()).unwrap();
+
+        assert_eq!(summary, vec![24000, 11000, 10000]);
+        assert_eq!(
+            CalorieCounting::part_two_from_summary(&summary, &Config::default()).unwrap(),
+            45000
+        );
+    }
+
+    #[test]
+    fn streams_the_same_answers_as_the_batch_solver() {
+        let input = ElfSnacks::default().generate(&mut Rng::new(1));
+        let parsed_input = CalorieCounting::parse(&input).unwrap();
+        let summary =
+            CalorieCounting::summarize_stream(&mut input.as_bytes(), &Config::default()).unwrap();
+
+        assert_eq!(
+            CalorieCounting::part_one_from_summary(&summary, &Config::default()).unwrap(),
+            CalorieCounting::part_one(&parsed_input).unwrap()
+        );
+        assert_eq!(
+            CalorieCounting::part_two_from_summary(&summary, &Config::default()).unwrap(),
+            CalorieCounting::part_two(&parsed_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn locates_invalid_calories_in_streams() {
+        let error = CalorieCounting::summarize_stream(
+            &mut "1000\n\n\n3x00\n".as_bytes(),
+            &Config::default(),
+        )
+        .unwrap_err()
+        .downcast::<LocatedError<ParseErrorKind>>()
+        .unwrap();
+
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+
+    #[test]
+    fn rejects_blank_snacks_when_streaming_too() {
+        for input in ["1\n\n\n\n2", "1\n\n\n\n\n2\n\n3"] {
+            let error = CalorieCounting::parse(input)
+                .unwrap_err()
+                .downcast::<LocatedError<ParseErrorKind>>()
+                .unwrap();
+            let stream_error =
+                CalorieCounting::summarize_stream(&mut input.as_bytes(), &Config::default())
+                    .unwrap_err()
+                    .downcast::<LocatedError<ParseErrorKind>>()
+                    .unwrap();
+
+            assert_eq!(
+                (stream_error.kind, stream_error.line, stream_error.column),
+                (error.kind, error.line, error.column)
+            );
+        }
+
+        for input in ["1\n\n\n2\n\n3", "\n\n\n1\n\n2\n\n3\n\n\n\n"] {
+            assert!(CalorieCounting::parse(input).is_ok());
+            assert!(
+                CalorieCounting::summarize_stream(&mut input.as_bytes(), &Config::default())
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn part_two_needs_three_elves() {
+        let input = CalorieCounting::parse("1\n\n2").unwrap();
+
+        assert!(CalorieCounting::part_two(&input).is_err());
+    }
+}
@@ -1,44 +1,145 @@
-use std::{fs::read_to_string, panic};
-
-fn main() {
-    let input = read_to_string("./input.txt").expect("Input file should be readable");
-
-    let calorie_count_by_elf = input.split("\n\n").map(|elf_calories| {
-        elf_calories
-            .trim()
-            .split('\n')
-            .map(|calories| {
-                calories
-                    .parse::<u32>()
-                    .expect("Calories should be an integer")
-            })
-            .sum::<u32>()
-    });
-
-    let ordered_calorie_counts_by_elf = {
-        let mut calories_list = calorie_count_by_elf.collect::<Vec<_>>();
-        calories_list.sort_unstable();
-        calories_list.reverse();
-        calories_list
-    };
+use std::env;
+
+use aoc_common::{
+    answer::{report, Part},
+    input::{program_name, InputSource, UsageError},
+    Result, Solution,
+};
+use calorie_counting::{
+    rebalance::{Planner, RebalancePlan},
+    report::TopElvesReport,
+    statistics::CalorieStatistics,
+    CalorieCounting, Config,
+};
+
+fn usage() -> String {
+    format!(
+        "Usage: {} [--report [--top <COUNT>] | --stats [--bins <COUNT>] | --rebalance [--exact]] \
+         [INPUT_PATH | -]",
+        program_name::<CalorieCounting>()
+    )
+}
+
+enum Mode {
+    Answers,
+    /// Names the elves carrying the most calories and lists their snacks.
+    Report {
+        top_elves: usize,
+    },
+    /// Describes how calories and snacks are spread among the elves.
+    Statistics {
+        bins: usize,
+    },
+    /// Plans how to share the snacks so that no elf carries much more than the others.
+    Rebalance {
+        planner: Planner,
+    },
+}
+
+fn count_flag(flag: &str, value: Option<String>) -> Result<usize, UsageError> {
+    value
+        .and_then(|value| value.parse::<usize>().ok())
+        .ok_or_else(|| UsageError(format!("{} should be followed by a count", flag)))
+}
+
+fn parse_args() -> Result<(Mode, InputSource), UsageError> {
+    let mut args = env::args().skip(1);
+
+    let mut report = false;
+    let mut top_elves = None;
+    let mut statistics = false;
+    let mut bins = None;
+    let mut rebalance = false;
+    let mut exact = false;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => report = true,
+            "--top" => top_elves = Some(count_flag(&arg, args.next())?),
+            "--stats" => statistics = true,
+            "--bins" => bins = Some(count_flag(&arg, args.next())?),
+            "--rebalance" => rebalance = true,
+            "--exact" => exact = true,
+            _ if input.is_none() && (arg == "-" || !arg.starts_with("--")) => input = Some(arg),
+            _ => {
+                return Err(UsageError(format!(
+                    "Unexpected argument \"{}\". {}",
+                    arg,
+                    usage()
+                )))
+            }
+        }
+    }
 
-    let biggest_calorie_count = ordered_calorie_counts_by_elf
-        .get(0)
-        .expect("Some elf should have the max calories");
+    if top_elves.is_some() && !report {
+        return Err(UsageError("--top only applies to --report".to_string()));
+    }
 
-    println!(
-        "The elf carrying the most calories is carrying {} calories.",
-        biggest_calorie_count
-    );
+    if bins.is_some() && !statistics {
+        return Err(UsageError("--bins only applies to --stats".to_string()));
+    }
 
-    if ordered_calorie_counts_by_elf.len() < 3 {
-        panic!("We should have at least 3 elves for the second part of the puzzle")
+    if exact && !rebalance {
+        return Err(UsageError(
+            "--exact only applies to --rebalance".to_string(),
+        ));
     }
 
-    let combined_top_three_calorie_counts = ordered_calorie_counts_by_elf[..3].iter().sum::<u32>();
+    let mode = match (report, statistics, rebalance) {
+        (true, false, false) => Mode::Report {
+            top_elves: top_elves.unwrap_or(Config::default().top_elves),
+        },
+        (false, true, false) => Mode::Statistics {
+            bins: bins.unwrap_or(10),
+        },
+        (false, false, true) => Mode::Rebalance {
+            planner: match exact {
+                true => Planner::Exact,
+                false => Planner::Greedy,
+            },
+        },
+        (false, false, false) => Mode::Answers,
+        _ => {
+            return Err(UsageError(
+                "Only one of --report, --stats and --rebalance can be given".to_string(),
+            ))
+        }
+    };
+
+    Ok((
+        mode,
+        InputSource::resolve::<CalorieCounting>(input.as_deref()),
+    ))
+}
+
+fn main() -> Result<()> {
+    let (mode, input_source) = parse_args()?;
+
+    let input = CalorieCounting::parse(&input_source.read()?)?;
+
+    match mode {
+        Mode::Answers => {
+            report(
+                Part::One,
+                format_args!(
+                    "The elf carrying the most calories is carrying {} calories.",
+                    CalorieCounting::part_one(&input)?
+                ),
+            );
+
+            report(
+                Part::Two,
+                format_args!(
+                    "The 3 elves carrying the most calories are carrying a combined amount of {} calories.",
+                    CalorieCounting::part_two(&input)?
+                ),
+            );
+        }
+        Mode::Report { top_elves } => println!("{}", TopElvesReport::new(&input, top_elves)),
+        Mode::Statistics { bins } => println!("{}", CalorieStatistics::new(&input, bins)),
+        Mode::Rebalance { planner } => println!("{}", RebalancePlan::new(&input, planner)?),
+    }
 
-    println!(
-        "The 3 elves carrying the most calories are carrying a combined amount of {} calories.",
-        combined_top_three_calorie_counts
-    );
+    Ok(())
 }
@@ -0,0 +1,537 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+};
+
+use aoc_common::Result;
+
+use crate::{CalorieCount, Calories, Elf};
+
+/// Past this many snacks, the exact planner could take longer than anyone would wait.
+pub const EXACT_SNACK_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Planner {
+    /// Longest processing time first: each snack, the biggest first, goes to the elf with the
+    /// lightest load so far. Fast, and never more than a third heavier than the best plan.
+    Greedy,
+    /// Searches every way to share the snacks, starting from the greedy plan and skipping the
+    /// ones which can't beat the best found so far.
+    Exact,
+}
+
+/// Which elf (out of `elf_count`) gets each of `snacks`, which should be sorted from the biggest.
+fn greedy_assignment(snacks: &[Calories], elf_count: usize) -> Vec<usize> {
+    let mut loads = (0..elf_count)
+        .map(|elf_index| Reverse((0, elf_index)))
+        .collect::<BinaryHeap<_>>();
+
+    snacks
+        .iter()
+        .map(|&calories| {
+            let mut lightest = loads.peek_mut().expect("There should be some elf");
+            let Reverse((load, elf_index)) = *lightest;
+
+            *lightest = Reverse((load + CalorieCount::from(calories), elf_index));
+
+            elf_index
+        })
+        .collect()
+}
+
+fn heaviest_load(snacks: &[Calories], assignment: &[usize], elf_count: usize) -> CalorieCount {
+    let mut loads = vec![0; elf_count];
+
+    for (&calories, &elf_index) in snacks.iter().zip(assignment) {
+        loads[elf_index] += CalorieCount::from(calories);
+    }
+
+    loads.into_iter().max().unwrap_or_default()
+}
+
+struct Search<'a> {
+    snacks: &'a [Calories],
+    lower_bound: CalorieCount,
+    loads: Vec<CalorieCount>,
+    assignment: Vec<usize>,
+    best_load: CalorieCount,
+    best_assignment: Vec<usize>,
+}
+
+impl Search<'_> {
+    fn assign(&mut self, snack_index: usize) {
+        let Some(&calories) = self.snacks.get(snack_index) else {
+            let heaviest_load = self.loads.iter().copied().max().unwrap_or_default();
+
+            if heaviest_load < self.best_load {
+                self.best_load = heaviest_load;
+                self.best_assignment.clone_from(&self.assignment);
+            }
+
+            return;
+        };
+
+        for elf_index in 0..self.loads.len() {
+            // Nothing beats a plan as good as the lower bound.
+            if self.best_load == self.lower_bound {
+                return;
+            }
+
+            // Elves with the same load are interchangeable, so trying one of them is enough.
+            if self.loads[..elf_index].contains(&self.loads[elf_index]) {
+                continue;
+            }
+
+            let load = self.loads[elf_index] + CalorieCount::from(calories);
+
+            if load >= self.best_load {
+                continue;
+            }
+
+            self.loads[elf_index] = load;
+            self.assignment[snack_index] = elf_index;
+
+            self.assign(snack_index + 1);
+
+            self.loads[elf_index] -= CalorieCount::from(calories);
+        }
+    }
+}
+
+fn exact_assignment(
+    snacks: &[Calories],
+    elf_count: usize,
+    lower_bound: CalorieCount,
+) -> Result<Vec<usize>> {
+    if snacks.len() > EXACT_SNACK_LIMIT {
+        return Err(format!(
+            "The exact planner only handles up to {} snacks, not {}. Use the greedy one instead",
+            EXACT_SNACK_LIMIT,
+            snacks.len()
+        )
+        .into());
+    }
+
+    let greedy_assignment = greedy_assignment(snacks, elf_count);
+
+    let mut search = Search {
+        snacks,
+        lower_bound,
+        loads: vec![0; elf_count],
+        assignment: vec![0; snacks.len()],
+        best_load: heaviest_load(snacks, &greedy_assignment, elf_count),
+        best_assignment: greedy_assignment,
+    };
+
+    search.assign(0);
+
+    Ok(search.best_assignment)
+}
+
+fn snack_counts(snacks: &[Calories]) -> HashMap<Calories, usize> {
+    let mut counts = HashMap::new();
+
+    for &calories in snacks {
+        *counts.entry(calories).or_default() += 1;
+    }
+
+    counts
+}
+
+/// Which elf should carry each of the new packs, so that as many snacks as possible stay with
+/// the elf already carrying them. Packs and elves are paired greedily, the most snacks in
+/// common first, which is close enough to the fewest transfers for packing.
+fn match_packs_to_elves(elves: &[Elf], packs: &[Vec<Calories>]) -> Vec<usize> {
+    let mut owners = HashMap::<Calories, Vec<(usize, usize)>>::new();
+
+    for (elf_index, elf) in elves.iter().enumerate() {
+        for (calories, count) in snack_counts(&elf.snacks) {
+            owners.entry(calories).or_default().push((elf_index, count));
+        }
+    }
+
+    // Only the elves owning some snack of a pack have anything in common with it.
+    let mut candidates = vec![];
+
+    for (pack_index, pack) in packs.iter().enumerate() {
+        let mut snacks_in_common = HashMap::<usize, usize>::new();
+
+        for (calories, count) in snack_counts(pack) {
+            for &(elf_index, owned) in owners.get(&calories).into_iter().flatten() {
+                *snacks_in_common.entry(elf_index).or_default() += count.min(owned);
+            }
+        }
+
+        candidates.extend(
+            snacks_in_common
+                .into_iter()
+                .map(|(elf_index, in_common)| (in_common, pack_index, elf_index)),
+        );
+    }
+
+    candidates.sort_unstable_by_key(|&(in_common, pack_index, elf_index)| {
+        (Reverse(in_common), pack_index, elf_index)
+    });
+
+    let mut elf_by_pack = vec![None; packs.len()];
+    let mut is_matched = vec![false; elves.len()];
+
+    for (_, pack_index, elf_index) in candidates {
+        if elf_by_pack[pack_index].is_none() && !is_matched[elf_index] {
+            elf_by_pack[pack_index] = Some(elf_index);
+            is_matched[elf_index] = true;
+        }
+    }
+
+    let mut unmatched_elves = (0..elves.len()).filter(|&elf_index| !is_matched[elf_index]);
+
+    elf_by_pack
+        .into_iter()
+        .map(|elf_index| {
+            elf_index.unwrap_or_else(|| unmatched_elves.next().expect("There should be an elf"))
+        })
+        .collect()
+}
+
+/// A snack handed from an elf to another, both by position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub calories: Calories,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// A way to share the snacks among the same elves so that the heaviest load is as light as the
+/// planner can make it, and the snacks which change hands to get there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalancePlan {
+    /// The elves with their snacks once the plan is carried out. Snacks that stay come first, in
+    /// their original order.
+    pub elves: Vec<Elf>,
+    pub transfers: Vec<Transfer>,
+    pub original_heaviest_load: CalorieCount,
+    /// No plan can get the heaviest load under this: the loads can't be more even than equal,
+    /// nor lighter than the biggest snack.
+    pub lower_bound: CalorieCount,
+}
+
+impl RebalancePlan {
+    pub fn new(elves: &[Elf], planner: Planner) -> Result<Self> {
+        let mut snacks = elves
+            .iter()
+            .flat_map(|elf| elf.snacks.iter().copied())
+            .collect::<Vec<_>>();
+
+        snacks.sort_unstable_by_key(|&calories| Reverse(calories));
+
+        let total = snacks
+            .iter()
+            .map(|&calories| CalorieCount::from(calories))
+            .sum::<CalorieCount>();
+
+        let lower_bound = match elves.len() {
+            0 => 0,
+            elf_count => total
+                .div_ceil(elf_count as CalorieCount)
+                .max(snacks.first().copied().unwrap_or_default().into()),
+        };
+
+        let assignment = match (planner, elves.len()) {
+            (_, 0) => vec![],
+            (Planner::Greedy, elf_count) => greedy_assignment(&snacks, elf_count),
+            (Planner::Exact, elf_count) => exact_assignment(&snacks, elf_count, lower_bound)?,
+        };
+
+        let mut packs = vec![vec![]; elves.len()];
+
+        for (&calories, &elf_index) in snacks.iter().zip(assignment.iter()) {
+            packs[elf_index].push(calories);
+        }
+
+        let mut new_elves = elves.to_vec();
+        let mut leaving = HashMap::<Calories, Vec<usize>>::new();
+        let mut arriving = HashMap::<Calories, Vec<usize>>::new();
+
+        for (pack, elf_index) in packs.iter().zip(match_packs_to_elves(elves, &packs)) {
+            let elf = &mut new_elves[elf_index];
+            let mut pack_counts = snack_counts(pack);
+
+            let (staying, left) = elf.snacks.iter().partition::<Vec<_>, _>(|calories| {
+                pack_counts
+                    .get_mut(calories)
+                    .filter(|count| **count > 0)
+                    .map(|count| *count -= 1)
+                    .is_some()
+            });
+
+            for calories in left {
+                leaving.entry(calories).or_default().push(elf.position);
+            }
+
+            // The pack is sorted from the biggest snack, so arrivals are too.
+            let arrived = pack.iter().copied().filter(|calories| {
+                pack_counts
+                    .get_mut(calories)
+                    .filter(|count| **count > 0)
+                    .map(|count| *count -= 1)
+                    .is_some()
+            });
+
+            elf.snacks = staying;
+
+            for calories in arrived {
+                arriving.entry(calories).or_default().push(elf.position);
+                elf.snacks.push(calories);
+            }
+        }
+
+        let mut transfers = leaving
+            .into_iter()
+            .flat_map(|(calories, from_elves)| {
+                from_elves
+                    .into_iter()
+                    .zip(arriving.remove(&calories).unwrap_or_default())
+                    .map(move |(from, to)| Transfer { calories, from, to })
+            })
+            .collect::<Vec<_>>();
+
+        transfers.sort_unstable_by_key(|transfer| {
+            (Reverse(transfer.calories), transfer.from, transfer.to)
+        });
+
+        Ok(Self {
+            elves: new_elves,
+            transfers,
+            original_heaviest_load: elves
+                .iter()
+                .map(Elf::calorie_count)
+                .max()
+                .unwrap_or_default(),
+            lower_bound,
+        })
+    }
+
+    pub fn heaviest_load(&self) -> CalorieCount {
+        self.elves
+            .iter()
+            .map(Elf::calorie_count)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for RebalancePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.elves.is_empty() {
+            return write!(f, "There are no elves to rebalance.");
+        }
+
+        writeln!(
+            f,
+            "The heaviest load goes from {} to {} calories (no plan can go under {}).",
+            self.original_heaviest_load,
+            self.heaviest_load(),
+            self.lower_bound
+        )?;
+
+        write!(f, "New loads:")?;
+
+        for elf in self.elves.iter() {
+            let snacks = elf
+                .snacks
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            write!(
+                f,
+                "\n  Elf {} carries {} calories: {}",
+                elf.position,
+                elf.calorie_count(),
+                snacks.join(", ")
+            )?;
+        }
+
+        match self.transfers.len() {
+            0 => write!(f, "\nNo snack needs to change hands."),
+            transfer_count => {
+                write!(f, "\n{} transfers:", transfer_count)?;
+
+                for transfer in self.transfers.iter() {
+                    write!(
+                        f,
+                        "\n  {} calories from elf {} to elf {}",
+                        transfer.calories, transfer.from, transfer.to
+                    )?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{random::Rng, Solution};
+
+    use crate::CalorieCounting;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn elves(input: &str) -> Vec<Elf> {
+        CalorieCounting::parse(input).unwrap()
+    }
+
+    /// Carries out `plan`'s transfers on `elves`, checking that every snack handed over was there.
+    fn carry_out(elves: &[Elf], plan: &RebalancePlan) -> Vec<Vec<Calories>> {
+        let mut snacks = elves
+            .iter()
+            .map(|elf| elf.snacks.clone())
+            .collect::<Vec<_>>();
+
+        for transfer in plan.transfers.iter() {
+            let from = &mut snacks[transfer.from - 1];
+            let index = from
+                .iter()
+                .position(|&calories| calories == transfer.calories)
+                .unwrap();
+
+            from.remove(index);
+            snacks[transfer.to - 1].push(transfer.calories);
+        }
+
+        snacks
+    }
+
+    fn sorted(mut snacks: Vec<Calories>) -> Vec<Calories> {
+        snacks.sort_unstable();
+        snacks
+    }
+
+    fn assert_consistent(elves: &[Elf], plan: &RebalancePlan) {
+        for (carried_out, elf) in carry_out(elves, plan).into_iter().zip(plan.elves.iter()) {
+            assert_eq!(sorted(carried_out), sorted(elf.snacks.clone()));
+        }
+    }
+
+    /// The lightest heaviest load, by trying every assignment.
+    fn brute_force(elves: &[Elf]) -> CalorieCount {
+        let snacks = elves
+            .iter()
+            .flat_map(|elf| elf.snacks.iter().copied())
+            .collect::<Vec<_>>();
+        let elf_count = elves.len();
+
+        (0..elf_count.pow(snacks.len() as u32))
+            .map(|mut code| {
+                let assignment = (0..snacks.len())
+                    .map(|_| {
+                        let elf_index = code % elf_count;
+                        code /= elf_count;
+                        elf_index
+                    })
+                    .collect::<Vec<_>>();
+
+                heaviest_load(&snacks, &assignment, elf_count)
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn evens_out_the_example_loads() {
+        let elves = elves(EXAMPLE);
+        let plan = RebalancePlan::new(&elves, Planner::Greedy).unwrap();
+
+        assert_eq!(
+            (plan.original_heaviest_load, plan.heaviest_load()),
+            (24000, 11000)
+        );
+        assert_eq!(plan.lower_bound, 11000);
+        assert_eq!(
+            plan.elves
+                .iter()
+                .map(Elf::calorie_count)
+                .collect::<Vec<_>>(),
+            vec![11000; 5]
+        );
+        assert_consistent(&elves, &plan);
+    }
+
+    #[test]
+    fn finds_better_plans_than_the_greedy_one() {
+        let elves = elves("3\n3\n\n2\n2\n2");
+
+        let greedy_plan = RebalancePlan::new(&elves, Planner::Greedy).unwrap();
+        let exact_plan = RebalancePlan::new(&elves, Planner::Exact).unwrap();
+
+        assert_eq!(greedy_plan.heaviest_load(), 7);
+        assert_consistent(&elves, &greedy_plan);
+
+        assert_eq!(exact_plan.heaviest_load(), 6);
+        assert_eq!(exact_plan.elves, elves);
+        assert_eq!(exact_plan.transfers, vec![]);
+    }
+
+    #[test]
+    fn keeps_snacks_with_their_elves_when_it_can() {
+        let elves = elves("5\n1\n\n3\n\n3");
+        let plan = RebalancePlan::new(&elves, Planner::Exact).unwrap();
+
+        assert_eq!(plan.heaviest_load(), 5);
+        assert_eq!(
+            plan.transfers,
+            vec![Transfer {
+                calories: 1,
+                from: 1,
+                to: 2
+            }]
+        );
+        assert_eq!(plan.elves[1].snacks, vec![3, 1]);
+    }
+
+    #[test]
+    fn plans_as_well_as_trying_every_assignment() {
+        let mut rng = Rng::new(25);
+
+        for _ in 0..30 {
+            let elves = (1..=rng.range(1..=3))
+                .map(|position| Elf {
+                    position,
+                    snacks: (0..rng.range(1..=3)).map(|_| rng.range(1..=20)).collect(),
+                })
+                .collect::<Vec<_>>();
+
+            let greedy_plan = RebalancePlan::new(&elves, Planner::Greedy).unwrap();
+            let exact_plan = RebalancePlan::new(&elves, Planner::Exact).unwrap();
+
+            assert_eq!(exact_plan.heaviest_load(), brute_force(&elves));
+            assert!(greedy_plan.heaviest_load() >= exact_plan.heaviest_load());
+            assert!(exact_plan.heaviest_load() >= exact_plan.lower_bound);
+
+            assert_consistent(&elves, &greedy_plan);
+            assert_consistent(&elves, &exact_plan);
+        }
+    }
+
+    #[test]
+    fn leaves_large_inputs_to_the_greedy_planner() {
+        let elves = elves(&vec!["1"; EXACT_SNACK_LIMIT + 1].join("\n\n"));
+
+        assert!(RebalancePlan::new(&elves, Planner::Exact).is_err());
+        assert_eq!(
+            RebalancePlan::new(&elves, Planner::Greedy)
+                .unwrap()
+                .heaviest_load(),
+            1
+        );
+        assert!(RebalancePlan::new(&[], Planner::Exact)
+            .unwrap()
+            .elves
+            .is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+
+use crate::{top_elves, CalorieCount, Elf};
+
+/// Which elves carry the most calories, and what they carry.
+pub struct TopElvesReport<'a> {
+    /// The top elves with their calorie counts, the most first.
+    pub top_elves: Vec<(&'a Elf, CalorieCount)>,
+    /// The elves left out of the top while carrying as many calories as the last elf in it.
+    pub left_out_ties: Vec<&'a Elf>,
+}
+
+impl<'a> TopElvesReport<'a> {
+    pub fn new(elves: &'a [Elf], count: usize) -> Self {
+        let top_elves = top_elves(elves, count);
+
+        // Tied elves make it into the top in the order they come in, so the ones left out all
+        // come after the last elf in it.
+        let left_out_ties = match top_elves.last() {
+            Some(&(last_top_elf, cut_off)) => elves
+                .iter()
+                .filter(|elf| {
+                    elf.position > last_top_elf.position && elf.calorie_count() == cut_off
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        Self {
+            top_elves,
+            left_out_ties,
+        }
+    }
+
+    /// The calorie count an elf needs to make it into the top.
+    pub fn cut_off(&self) -> Option<CalorieCount> {
+        self.top_elves
+            .last()
+            .map(|&(_, calorie_count)| calorie_count)
+    }
+}
+
+impl fmt::Display for TopElvesReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(cut_off) = self.cut_off() else {
+            return write!(f, "There are no elves to report on.");
+        };
+
+        write!(f, "Top {} elves by calories:", self.top_elves.len())?;
+
+        for (rank, &(elf, calorie_count)) in self.top_elves.iter().enumerate() {
+            let snacks = elf
+                .snacks
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            write!(
+                f,
+                "\n{:>3}. Elf {} carries {} calories: {}",
+                rank + 1,
+                elf.position,
+                calorie_count,
+                snacks.join(", ")
+            )?;
+
+            if calorie_count == cut_off && !self.left_out_ties.is_empty() {
+                write!(f, " (tied at the cut-off)")?;
+            }
+        }
+
+        if !self.left_out_ties.is_empty() {
+            let left_out_elves = self
+                .left_out_ties
+                .iter()
+                .map(|elf| format!("elf {}", elf.position))
+                .collect::<Vec<_>>();
+
+            write!(
+                f,
+                "\nAlso carrying {} calories, but left out: {}",
+                cut_off,
+                left_out_elves.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use crate::CalorieCounting;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn names_the_top_elves_and_lists_their_snacks() {
+        let elves = CalorieCounting::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            TopElvesReport::new(&elves, 3).to_string(),
+            "Top 3 elves by calories:\n  \
+               1. Elf 4 carries 24000 calories: 7000, 8000, 9000\n  \
+               2. Elf 3 carries 11000 calories: 5000, 6000\n  \
+               3. Elf 5 carries 10000 calories: 10000"
+        );
+    }
+
+    #[test]
+    fn flags_ties_at_the_cut_off() {
+        let elves = CalorieCounting::parse("1\n\n2\n\n1\n1\n\n3\n\n2").unwrap();
+        let report = TopElvesReport::new(&elves, 2);
+
+        assert_eq!(report.cut_off(), Some(2));
+        assert_eq!(
+            report
+                .left_out_ties
+                .iter()
+                .map(|elf| elf.position)
+                .collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Top 2 elves by calories:\n  \
+               1. Elf 4 carries 3 calories: 3\n  \
+               2. Elf 2 carries 2 calories: 2 (tied at the cut-off)\n\
+             Also carrying 2 calories, but left out: elf 3, elf 5"
+        );
+    }
+
+    #[test]
+    fn reports_every_elf_when_there_are_fewer_than_asked() {
+        let elves = CalorieCounting::parse("1\n\n2").unwrap();
+        let report = TopElvesReport::new(&elves, 3);
+
+        assert_eq!(report.top_elves.len(), 2);
+        assert!(report.left_out_ties.is_empty());
+        assert!(TopElvesReport::new(&[], 3)
+            .to_string()
+            .starts_with("There are no elves"));
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+
+use crate::{CalorieCount, Elf};
+
+/// The percentiles reported besides the median, nearest-rank like the benchmarks' ones.
+const PERCENTILES: [usize; 5] = [10, 25, 75, 90, 99];
+
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Descriptive statistics of a set of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub total: CalorieCount,
+    pub mean: f64,
+    /// The middle value, or the mean of the two middle ones when there is an even count.
+    pub median: f64,
+    /// The population standard deviation, since the values are every elf rather than a sample.
+    pub standard_deviation: f64,
+    pub min: CalorieCount,
+    pub max: CalorieCount,
+    /// Each of `PERCENTILES` with its value.
+    pub percentiles: Vec<(usize, CalorieCount)>,
+}
+
+impl Summary {
+    /// Summarizes `values`, unless there are none.
+    pub fn of(values: impl IntoIterator<Item = CalorieCount>) -> Option<Self> {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+
+        values.sort_unstable();
+
+        let (&min, &max) = (values.first()?, values.last()?);
+
+        let count = values.len();
+        let total = values.iter().sum::<CalorieCount>();
+        let mean = total as f64 / count as f64;
+
+        let median = match count % 2 {
+            0 => (values[count / 2 - 1] + values[count / 2]) as f64 / 2.0,
+            _ => values[count / 2] as f64,
+        };
+
+        let variance = values
+            .iter()
+            .map(|&value| (value as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&rank| (rank, values[(rank * count).div_ceil(100).max(1) - 1]))
+            .collect();
+
+        Some(Self {
+            count,
+            total,
+            mean,
+            median,
+            standard_deviation: variance.sqrt(),
+            min,
+            max,
+            percentiles,
+        })
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  count {}, total {}", self.count, self.total)?;
+        writeln!(
+            f,
+            "  mean {:.1}, median {:.1}, standard deviation {:.1}",
+            self.mean, self.median, self.standard_deviation
+        )?;
+
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(rank, value)| format!("p{} {}", rank, value))
+            .collect::<Vec<_>>();
+
+        write!(
+            f,
+            "  min {}, {}, max {}",
+            self.min,
+            percentiles.join(", "),
+            self.max
+        )
+    }
+}
+
+/// How many values fall in each of a run of equally wide ranges, from the smallest value to the
+/// largest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub start: CalorieCount,
+    pub bin_width: CalorieCount,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Sorts `values` into at most `bins` ranges, as few as it takes to cover them all.
+    pub fn new(values: &[CalorieCount], bins: usize) -> Self {
+        let (Some(&start), Some(&end)) = (values.iter().min(), values.iter().max()) else {
+            return Self {
+                start: 0,
+                bin_width: 1,
+                counts: vec![],
+            };
+        };
+
+        let bin_width = (end - start + 1).div_ceil(bins.max(1) as CalorieCount);
+        let mut counts = vec![0; ((end - start) / bin_width) as usize + 1];
+
+        for &value in values {
+            counts[((value - start) / bin_width) as usize] += 1;
+        }
+
+        Self {
+            start,
+            bin_width,
+            counts,
+        }
+    }
+
+    /// The smallest and largest values each bin can hold.
+    pub fn bin_ranges(&self) -> impl Iterator<Item = (CalorieCount, CalorieCount)> + '_ {
+        (0..self.counts.len() as CalorieCount).map(|bin| {
+            let bin_start = self.start + bin * self.bin_width;
+
+            (bin_start, bin_start + self.bin_width - 1)
+        })
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let largest_count = self.counts.iter().copied().max().unwrap_or_default();
+
+        let label_width = self
+            .bin_ranges()
+            .last()
+            .map_or(0, |(_, bin_end)| bin_end.to_string().len());
+
+        for (index, ((bin_start, bin_end), &count)) in
+            self.bin_ranges().zip(self.counts.iter()).enumerate()
+        {
+            // Bars are scaled to the largest bin, but never hide a bin with anything in it.
+            let bar_length = match count {
+                0 => 0,
+                _ => (count * HISTOGRAM_WIDTH / largest_count).max(1),
+            };
+
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "  {:>width$} - {:>width$} | {:<bar_width$} {}",
+                bin_start,
+                bin_end,
+                "#".repeat(bar_length),
+                count,
+                width = label_width,
+                bar_width = HISTOGRAM_WIDTH
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How calories and snacks are spread among the elves.
+pub struct CalorieStatistics {
+    pub calories_per_elf: Option<Summary>,
+    pub snacks_per_elf: Option<Summary>,
+    pub histogram: Histogram,
+}
+
+impl CalorieStatistics {
+    pub fn new(elves: &[Elf], bins: usize) -> Self {
+        let calorie_counts = elves
+            .iter()
+            .map(|elf| elf.calorie_count())
+            .collect::<Vec<_>>();
+
+        Self {
+            calories_per_elf: Summary::of(calorie_counts.iter().copied()),
+            snacks_per_elf: Summary::of(elves.iter().map(|elf| elf.snacks.len() as CalorieCount)),
+            histogram: Histogram::new(&calorie_counts, bins),
+        }
+    }
+}
+
+impl fmt::Display for CalorieStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (Some(calories_per_elf), Some(snacks_per_elf)) =
+            (&self.calories_per_elf, &self.snacks_per_elf)
+        else {
+            return write!(f, "There are no elves to report on.");
+        };
+
+        writeln!(f, "Calories per elf:\n{}", calories_per_elf)?;
+        writeln!(f, "Snacks per elf:\n{}", snacks_per_elf)?;
+        write!(f, "Elves by calories carried:\n{}", self.histogram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use crate::CalorieCounting;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn summarizes_calories_and_snacks_per_elf() {
+        let statistics = CalorieStatistics::new(&CalorieCounting::parse(EXAMPLE).unwrap(), 5);
+
+        let calories_per_elf = statistics.calories_per_elf.unwrap();
+
+        assert_eq!((calories_per_elf.count, calories_per_elf.total), (5, 55000));
+        assert_eq!(
+            (calories_per_elf.mean, calories_per_elf.median),
+            (11000.0, 10000.0)
+        );
+        assert!((calories_per_elf.standard_deviation - 6985.7).abs() < 0.1);
+        assert_eq!((calories_per_elf.min, calories_per_elf.max), (4000, 24000));
+        assert_eq!(
+            calories_per_elf.percentiles,
+            vec![
+                (10, 4000),
+                (25, 6000),
+                (75, 11000),
+                (90, 24000),
+                (99, 24000)
+            ]
+        );
+
+        let snacks_per_elf = statistics.snacks_per_elf.unwrap();
+
+        assert_eq!((snacks_per_elf.total, snacks_per_elf.median), (10, 2.0));
+    }
+
+    #[test]
+    fn takes_the_middle_of_even_counts_as_the_median() {
+        assert_eq!(Summary::of([4, 1, 3, 2]).unwrap().median, 2.5);
+        assert_eq!(Summary::of([]), None);
+    }
+
+    #[test]
+    fn bins_values_from_the_smallest_to_the_largest() {
+        let histogram = Histogram::new(&[6000, 4000, 11000, 24000, 10000], 5);
+
+        assert_eq!((histogram.start, histogram.bin_width), (4000, 4001));
+        assert_eq!(histogram.counts, vec![2, 2, 0, 0, 1]);
+        assert_eq!(
+            histogram.to_string().lines().next().unwrap(),
+            format!("   4000 -  8000 | {} 2", "#".repeat(40))
+        );
+
+        assert_eq!(Histogram::new(&[7, 7], 10).counts, vec![2]);
+        assert_eq!(Histogram::new(&[1, 2, 3], 10).counts, vec![1, 1, 1]);
+    }
+}
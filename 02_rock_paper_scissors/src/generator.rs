@@ -0,0 +1,56 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+pub struct StrategyGuide {
+    pub rounds: usize,
+}
+
+impl Default for StrategyGuide {
+    fn default() -> Self {
+        Self { rounds: 2500 }
+    }
+}
+
+impl Generator for StrategyGuide {
+    fn scaled(scale: usize) -> Self {
+        Self {
+            rounds: Self::default().rounds * scale,
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.rounds)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&['A', 'B', 'C']),
+                    rng.choose(&['X', 'Y', 'Z'])
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::RockPaperScissors;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        for seed in 0..10 {
+            let input = StrategyGuide::default().generate(&mut Rng::new(seed));
+            let rounds = RockPaperScissors::parse(&input).unwrap();
+
+            assert_eq!(rounds.len(), 2500);
+            assert!(RockPaperScissors::part_one(&rounds).unwrap() <= 2500 * 9);
+            assert!(RockPaperScissors::part_two(&rounds).unwrap() <= 2500 * 9);
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<RockPaperScissors>(2, 200).unwrap();
+    }
+}
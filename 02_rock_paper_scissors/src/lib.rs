@@ -0,0 +1,394 @@
+pub mod generator;
+
+use std::{fmt, io::BufRead, panic};
+
+use aoc_common::{
+    diagnostic::{self, parse_lines, Span},
+    stream::{parse_stream_lines, StreamingSolution},
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MalformedRound,
+    InvalidOpponentShape(char),
+    InvalidSecondColumn(char),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedRound => write!(
+                f,
+                "A round should be the opponent's shape and the second column separated by a space"
+            ),
+            Self::InvalidOpponentShape(shape_char) => write!(
+                f,
+                "Character {} isn't a valid shape representation for the opponent",
+                shape_char
+            ),
+            Self::InvalidSecondColumn(column_char) => write!(
+                f,
+                "Character {} isn't a valid second column value, which should be X, Y or Z",
+                column_char
+            ),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+#[derive(Debug)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub fn from_player_representation(shape_char: &char) -> Self {
+        match shape_char {
+            'X' => Self::Rock,
+            'Y' => Self::Paper,
+            'Z' => Self::Scissors,
+            _ => panic!(
+                "Character {} isn't a valid shape representation for the player",
+                shape_char
+            ),
+        }
+    }
+
+    pub fn from_opponent_representation(shape_char: &char) -> Self {
+        match shape_char {
+            'A' => Self::Rock,
+            'B' => Self::Paper,
+            'C' => Self::Scissors,
+            _ => panic!(
+                "Character {} isn't a valid shape representation for the opponent",
+                shape_char
+            ),
+        }
+    }
+
+    pub fn score(&self) -> u8 {
+        match self {
+            Self::Rock => 1,
+            Self::Paper => 2,
+            Self::Scissors => 3,
+        }
+    }
+}
+
+pub enum RoundResult {
+    Win,
+    Draw,
+    Lose,
+}
+
+impl RoundResult {
+    pub fn from(result_char: &char) -> Self {
+        match result_char {
+            'X' => Self::Lose,
+            'Y' => Self::Draw,
+            'Z' => Self::Win,
+            _ => panic!(
+                "Character {} isn't a valid round result representation",
+                result_char
+            ),
+        }
+    }
+}
+
+pub fn get_round_score(opponent_shape: &Shape, player_shape: &Shape) -> u8 {
+    let match_score = match (opponent_shape, player_shape) {
+        (Shape::Rock, Shape::Rock) => 3,
+        (Shape::Rock, Shape::Paper) => 6,
+        (Shape::Rock, Shape::Scissors) => 0,
+        (Shape::Paper, Shape::Rock) => 0,
+        (Shape::Paper, Shape::Paper) => 3,
+        (Shape::Paper, Shape::Scissors) => 6,
+        (Shape::Scissors, Shape::Rock) => 6,
+        (Shape::Scissors, Shape::Paper) => 0,
+        (Shape::Scissors, Shape::Scissors) => 3,
+    };
+
+    match_score + player_shape.score()
+}
+
+pub fn get_shape_to_play(opponent_shape: &Shape, desired_result: &RoundResult) -> Shape {
+    match (opponent_shape, desired_result) {
+        (Shape::Rock, RoundResult::Win) => Shape::Paper,
+        (Shape::Rock, RoundResult::Draw) => Shape::Rock,
+        (Shape::Rock, RoundResult::Lose) => Shape::Scissors,
+        (Shape::Paper, RoundResult::Win) => Shape::Scissors,
+        (Shape::Paper, RoundResult::Draw) => Shape::Paper,
+        (Shape::Paper, RoundResult::Lose) => Shape::Rock,
+        (Shape::Scissors, RoundResult::Win) => Shape::Rock,
+        (Shape::Scissors, RoundResult::Draw) => Shape::Scissors,
+        (Shape::Scissors, RoundResult::Lose) => Shape::Paper,
+    }
+}
+
+pub fn calculate_score_with_misinterpreted_guide(round_chars: &[(char, char)]) -> u32 {
+    round_chars
+        .iter()
+        .map(|(opponent_shape_char, player_shape_char)| {
+            get_round_score(
+                &Shape::from_opponent_representation(opponent_shape_char),
+                &Shape::from_player_representation(player_shape_char),
+            ) as u32
+        })
+        .sum()
+}
+
+pub fn calculate_score_with_correct_guide(round_chars: &[(char, char)]) -> u32 {
+    round_chars
+        .iter()
+        .map(|(opponent_shape_char, desired_result)| {
+            let player_shape_to_play = get_shape_to_play(
+                &Shape::from_opponent_representation(opponent_shape_char),
+                &RoundResult::from(desired_result),
+            );
+
+            get_round_score(
+                &Shape::from_opponent_representation(opponent_shape_char),
+                &player_shape_to_play,
+            ) as u32
+        })
+        .sum()
+}
+
+fn parse_round_chars(round_line: &str) -> Result<(char, char), ParseError> {
+    let mut round_chars = round_line.char_indices();
+
+    match (
+        round_chars.next(),
+        round_chars.next(),
+        round_chars.next(),
+        round_chars.next(),
+    ) {
+        (
+            Some((_, opponent_shape_char)),
+            Some((_, ' ')),
+            Some((player_shape_index, player_shape_char)),
+            None,
+        ) => {
+            if !matches!(opponent_shape_char, 'A'..='C') {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidOpponentShape(opponent_shape_char),
+                    Span::new(0, opponent_shape_char.len_utf8()),
+                ));
+            }
+
+            if !matches!(player_shape_char, 'X'..='Z') {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidSecondColumn(player_shape_char),
+                    Span::new(
+                        player_shape_index,
+                        player_shape_index + player_shape_char.len_utf8(),
+                    ),
+                ));
+            }
+
+            Ok((opponent_shape_char, player_shape_char))
+        }
+        _ => Err(ParseError::at(
+            round_line,
+            round_line,
+            ParseErrorKind::MalformedRound,
+        )),
+    }
+}
+
+pub struct RockPaperScissors;
+
+impl Solution for RockPaperScissors {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "rock_paper_scissors";
+
+    type Input = Vec<(char, char)>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    type Generator = generator::StrategyGuide;
+
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input, parse_round_chars)?)
+    }
+
+    fn part_one_with(round_chars: &Self::Input, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(calculate_score_with_misinterpreted_guide(round_chars))
+    }
+
+    fn part_two_with(round_chars: &Self::Input, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(calculate_score_with_correct_guide(round_chars))
+    }
+}
+
+impl StreamingSolution for RockPaperScissors {
+    /// The scores following the misinterpreted and the correct guide.
+    type Summary = (u32, u32);
+
+    fn summarize_stream(reader: &mut dyn BufRead, _: &Self::Config) -> Result<Self::Summary> {
+        let mut scores = (0, 0);
+
+        parse_stream_lines(reader, parse_round_chars, |round_chars| {
+            scores.0 += calculate_score_with_misinterpreted_guide(&[round_chars]);
+            scores.1 += calculate_score_with_correct_guide(&[round_chars]);
+        })?;
+
+        Ok(scores)
+    }
+
+    fn part_one_from_summary(scores: &Self::Summary, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(scores.0)
+    }
+
+    fn part_two_from_summary(scores: &Self::Summary, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(scores.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        diagnostic::LocatedError,
+        generator::Generator,
+        normalize::{normalize, variants, NormalizedReader},
+        random::Rng,
+    };
+
+    use crate::generator::StrategyGuide;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn parse_error(input: &str) -> LocatedError<ParseErrorKind> {
+        *RockPaperScissors::parse(input)
+            .unwrap_err()
+            .downcast::<LocatedError<ParseErrorKind>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_round_characters() {
+        let rounds = RockPaperScissors::parse(EXAMPLE).unwrap();
+
+        assert_eq!(rounds, vec![('A', 'Y'), ('B', 'X'), ('C', 'Z')]);
+    }
+
+    #[test]
+    fn rejects_unknown_opponent_shapes() {
+        let error = parse_error("A Y\nD X");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidOpponentShape('D'));
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn rejects_unknown_second_column() {
+        let error = parse_error("A W");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidSecondColumn('W'));
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+
+    #[test]
+    fn rejects_malformed_rounds() {
+        assert_eq!(parse_error("AY").kind, ParseErrorKind::MalformedRound);
+        assert_eq!(parse_error("A Y Z").kind, ParseErrorKind::MalformedRound);
+    }
+
+    #[test]
+    fn scores_rounds_by_outcome_and_shape() {
+        assert_eq!(get_round_score(&Shape::Rock, &Shape::Paper), 8);
+        assert_eq!(get_round_score(&Shape::Paper, &Shape::Rock), 1);
+        assert_eq!(get_round_score(&Shape::Scissors, &Shape::Scissors), 6);
+    }
+
+    #[test]
+    fn picks_the_shape_for_the_desired_result() {
+        assert!(matches!(
+            get_shape_to_play(&Shape::Rock, &RoundResult::Draw),
+            Shape::Rock
+        ));
+        assert!(matches!(
+            get_shape_to_play(&Shape::Paper, &RoundResult::Lose),
+            Shape::Rock
+        ));
+        assert!(matches!(
+            get_shape_to_play(&Shape::Scissors, &RoundResult::Win),
+            Shape::Rock
+        ));
+    }
+
+    #[test]
+    fn streams_the_same_answers_as_the_batch_solver() {
+        let input = StrategyGuide::default().generate(&mut Rng::new(1));
+        let parsed_input = RockPaperScissors::parse(&input).unwrap();
+
+        assert_eq!(
+            RockPaperScissors::summarize_stream(&mut input.as_bytes(), &()).unwrap(),
+            (
+                RockPaperScissors::part_one(&parsed_input).unwrap(),
+                RockPaperScissors::part_two(&parsed_input).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = RockPaperScissors::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                RockPaperScissors::part_one(&input).unwrap(),
+                15,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                RockPaperScissors::part_two(&input).unwrap(),
+                12,
+                "{}",
+                variant
+            );
+
+            let summary = RockPaperScissors::summarize_stream(
+                &mut NormalizedReader::new(text.as_bytes()),
+                &(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                RockPaperScissors::part_one_from_summary(&summary, &()).unwrap(),
+                15,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                RockPaperScissors::part_two_from_summary(&summary, &()).unwrap(),
+                12,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = RockPaperScissors::parse(EXAMPLE).unwrap();
+
+        assert_eq!(RockPaperScissors::part_one(&input).unwrap(), 15);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = RockPaperScissors::parse(EXAMPLE).unwrap();
+
+        assert_eq!(RockPaperScissors::part_two(&input).unwrap(), 12);
+    }
+}
@@ -0,0 +1,144 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{generator::Generator, random::Rng};
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Groups of three rucksacks where each rucksack shares exactly one item between its
+/// compartments and each group shares exactly one badge item, as the puzzle guarantees.
+pub struct ElfGroups {
+    pub groups: usize,
+    pub compartment_size: RangeInclusive<usize>,
+}
+
+impl Default for ElfGroups {
+    fn default() -> Self {
+        Self {
+            groups: 100,
+            compartment_size: 4..=24,
+        }
+    }
+}
+
+impl ElfGroups {
+    fn compartment(
+        &self,
+        rng: &mut Rng,
+        size: usize,
+        required_items: &[char],
+        pool: &[char],
+    ) -> String {
+        let mut items = required_items.to_vec();
+
+        while items.len() < size {
+            items.push(*rng.choose(pool));
+        }
+
+        rng.shuffle(&mut items);
+
+        items.into_iter().collect()
+    }
+
+    fn rucksack(&self, rng: &mut Rng, badge: char, allowed_items: &[char]) -> String {
+        let size = rng.range(self.compartment_size.clone());
+
+        let shared_item = match rng.chance(0.1) {
+            true => badge,
+            false => *rng.choose(allowed_items),
+        };
+
+        let mut pool = allowed_items
+            .iter()
+            .copied()
+            .filter(|&item| item != shared_item)
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut pool);
+
+        let (left_pool, right_pool) = pool.split_at(pool.len() / 2);
+
+        let badge_in_left = left_pool.contains(&badge) || shared_item == badge;
+
+        let left_required = match badge_in_left {
+            true => vec![shared_item, badge],
+            false => vec![shared_item],
+        };
+        let right_required = match badge_in_left {
+            true => vec![shared_item],
+            false => vec![shared_item, badge],
+        };
+
+        self.compartment(rng, size, &left_required, left_pool)
+            + &self.compartment(rng, size, &right_required, right_pool)
+    }
+}
+
+impl Generator for ElfGroups {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            groups: default.groups * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let items = ITEMS.chars().collect::<Vec<_>>();
+
+        (0..self.groups)
+            .flat_map(|_| {
+                let badge = *rng.choose(&items);
+
+                // Every other item is left out of one of the three rucksacks, so the badge is the
+                // only item all of them can have in common.
+                let excluded_from = items.iter().map(|_| rng.range(0..=2)).collect::<Vec<_>>();
+
+                (0..3)
+                    .map(|rucksack_index| {
+                        let allowed_items = items
+                            .iter()
+                            .zip(excluded_from.iter())
+                            .filter(|&(&item, &excluded)| {
+                                item == badge || excluded != rucksack_index
+                            })
+                            .map(|(&item, _)| item)
+                            .collect::<Vec<_>>();
+
+                        self.rucksack(rng, badge, &allowed_items) + "\n"
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::{get_elf_group_badge_item, RucksackReorganization};
+
+    #[test]
+    fn generates_rucksacks_with_a_single_shared_item() {
+        for seed in 0..10 {
+            let input = ElfGroups::default().generate(&mut Rng::new(seed));
+            let rucksacks = RucksackReorganization::parse(&input).unwrap();
+
+            assert_eq!(rucksacks.len(), 300);
+
+            for rucksack in rucksacks.iter() {
+                assert_eq!(rucksack.common_items_in_compartments().len(), 1);
+            }
+
+            for group in rucksacks.chunks(3) {
+                assert!(get_elf_group_badge_item(group).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<RucksackReorganization>(3, 200).unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+pub mod generator;
+
+use std::{collections::HashSet, fmt};
+
+use aoc_common::{
+    config::{self, integer_at_least, unknown_key},
+    diagnostic::{self, parse_lines, Span},
+    toml::Value,
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnevenCompartments,
+    InvalidItem(char),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnevenCompartments => write!(
+                f,
+                "A rucksack should have the same number of elements on both compartments"
+            ),
+            Self::InvalidItem(item_char) => {
+                write!(
+                    f,
+                    "Item {} should be a letter from a to z or A to Z",
+                    item_char
+                )
+            }
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+#[derive(Debug)]
+pub struct Rucksack {
+    left: HashSet<char>,
+    right: HashSet<char>,
+}
+
+impl Rucksack {
+    pub fn unique_items(&self) -> HashSet<char> {
+        self.left.union(&self.right).copied().collect()
+    }
+
+    pub fn common_items_in_compartments(&self) -> HashSet<&char> {
+        self.left.intersection(&self.right).collect()
+    }
+}
+
+impl TryFrom<&str> for Rucksack {
+    type Error = ParseError;
+
+    fn try_from(contents: &str) -> Result<Self, Self::Error> {
+        if let Some((index, item_char)) = contents
+            .char_indices()
+            .find(|(_, item_char)| !item_char.is_ascii_alphabetic())
+        {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidItem(item_char),
+                Span::new(index, index + item_char.len_utf8()),
+            ));
+        }
+
+        match contents.len() % 2 {
+            0 => {
+                let (left_contents, right_contents) = contents.split_at(contents.len() / 2);
+
+                Ok(Self {
+                    left: HashSet::from_iter(left_contents.chars()),
+                    right: HashSet::from_iter(right_contents.chars()),
+                })
+            }
+            _ => Err(ParseError::at(
+                contents,
+                contents,
+                ParseErrorKind::UnevenCompartments,
+            )),
+        }
+    }
+}
+
+pub fn get_item_priority(item_char: &char) -> u32 {
+    match item_char {
+        'A'..='Z' => *item_char as u32 - 38,
+        'a'..='z' => *item_char as u32 - 96,
+        _ => 0,
+    }
+}
+
+pub fn get_elf_group_badge_item(rucksacks: &[Rucksack]) -> Option<char> {
+    rucksacks
+        .iter()
+        .map(|rucksack| rucksack.unique_items())
+        .reduce(|common_items, rucksack_items| {
+            common_items
+                .intersection(&rucksack_items)
+                .copied()
+                .collect::<HashSet<_>>()
+        })
+        .expect("Should provide at least one rucksack to get the badge item")
+        .iter()
+        .last()
+        .copied()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// How many elves share a badge.
+    pub group_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { group_size: 3 }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &["group_size"];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "group_size" => self.group_size = integer_at_least(key, value, 1)?,
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RucksackReorganization;
+
+impl Solution for RucksackReorganization {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "rucksack_reorganization";
+
+    type Input = Vec<Rucksack>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    type Generator = generator::ElfGroups;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input, |line| Rucksack::try_from(line))?)
+    }
+
+    fn part_one_with(rucksacks: &Self::Input, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(rucksacks
+            .iter()
+            .filter_map(|rucksack| {
+                rucksack
+                    .common_items_in_compartments()
+                    .iter()
+                    .last()
+                    .copied()
+            })
+            .map(get_item_priority)
+            .sum())
+    }
+
+    fn part_two_with(rucksacks: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(rucksacks
+            .chunks(config.group_size)
+            .filter_map(get_elf_group_badge_item)
+            .map(|v| get_item_priority(&v))
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::normalize::{normalize, variants};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn splits_contents_into_compartments() {
+        let rucksack = Rucksack::try_from("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        assert_eq!(rucksack.left, HashSet::from_iter("vJrwpWtwJgWr".chars()));
+        assert_eq!(rucksack.right, HashSet::from_iter("hcsFMMfFFhFp".chars()));
+        assert_eq!(
+            rucksack.common_items_in_compartments(),
+            HashSet::from([&'p'])
+        );
+    }
+
+    #[test]
+    fn rejects_uneven_compartments() {
+        let error = Rucksack::try_from("abc").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::UnevenCompartments);
+        assert_eq!(error.span, Span::new(0, 3));
+    }
+
+    #[test]
+    fn rejects_non_letter_items() {
+        let error = Rucksack::try_from("ab1d").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidItem('1'));
+        assert_eq!(error.span, Span::new(2, 3));
+    }
+
+    #[test]
+    fn prioritizes_lowercase_before_uppercase() {
+        assert_eq!(get_item_priority(&'a'), 1);
+        assert_eq!(get_item_priority(&'z'), 26);
+        assert_eq!(get_item_priority(&'A'), 27);
+        assert_eq!(get_item_priority(&'Z'), 52);
+    }
+
+    #[test]
+    fn finds_the_group_badge() {
+        let rucksacks = RucksackReorganization::parse(EXAMPLE).unwrap();
+
+        assert_eq!(get_elf_group_badge_item(&rucksacks[..3]), Some('r'));
+        assert_eq!(get_elf_group_badge_item(&rucksacks[3..]), Some('Z'));
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = RucksackReorganization::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                RucksackReorganization::part_one(&input).unwrap(),
+                157,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                RucksackReorganization::part_two(&input).unwrap(),
+                70,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = RucksackReorganization::parse(EXAMPLE).unwrap();
+
+        assert_eq!(RucksackReorganization::part_one(&input).unwrap(), 157);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = RucksackReorganization::parse(EXAMPLE).unwrap();
+
+        assert_eq!(RucksackReorganization::part_two(&input).unwrap(), 70);
+    }
+
+    #[test]
+    fn finds_badges_of_configured_group_sizes() {
+        let input = RucksackReorganization::parse("abab\nbcbc\nxyxy\nyzyz").unwrap();
+        let config = Config { group_size: 2 };
+
+        assert_eq!(
+            RucksackReorganization::part_two_with(&input, &config).unwrap(),
+            27
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+pub struct SectionAssignments {
+    pub pairs: usize,
+    pub sections: u32,
+}
+
+impl Default for SectionAssignments {
+    fn default() -> Self {
+        Self {
+            pairs: 1000,
+            sections: 99,
+        }
+    }
+}
+
+impl SectionAssignments {
+    fn range(&self, rng: &mut Rng) -> String {
+        let start = rng.range(1..=self.sections);
+        let end = rng.range(start..=self.sections);
+
+        format!("{}-{}", start, end)
+    }
+}
+
+impl Generator for SectionAssignments {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            pairs: default.pairs * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.pairs)
+            .map(|_| format!("{},{}\n", self.range(rng), self.range(rng)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::CampCleanup;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        for seed in 0..10 {
+            let input = SectionAssignments::default().generate(&mut Rng::new(seed));
+            let pairs = CampCleanup::parse(&input).unwrap();
+
+            assert_eq!(pairs.len(), 1000);
+            assert!(
+                CampCleanup::part_one(&pairs).unwrap() <= CampCleanup::part_two(&pairs).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<CampCleanup>(4, 200).unwrap();
+    }
+}
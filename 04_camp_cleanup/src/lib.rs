@@ -0,0 +1,344 @@
+pub mod generator;
+
+use std::{cmp::Ordering, fmt, io::BufRead};
+
+use aoc_common::{
+    binary::{Binary, Decoder, Encoder},
+    cache::CachedSolution,
+    diagnostic::{self, parse_lines, Span},
+    stream::{parse_stream_lines, StreamingSolution},
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingPairSeparator,
+    MissingRangeSeparator,
+    InvalidRangeStart,
+    InvalidRangeEnd,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPairSeparator => write!(f, "An elf pair should be comma separated"),
+            Self::MissingRangeSeparator => write!(f, "A range should be dash separated"),
+            Self::InvalidRangeStart => write!(f, "A range start value should be an integer"),
+            Self::InvalidRangeEnd => write!(f, "A range end value should be an integer"),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub struct SectionRange {
+    start: u32,
+    end: u32,
+}
+
+impl TryFrom<&str> for SectionRange {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (start_section, end_section) = value
+            .split_once('-')
+            .ok_or_else(|| ParseError::at(value, value, ParseErrorKind::MissingRangeSeparator))?;
+
+        Ok(Self {
+            start: start_section.parse::<u32>().map_err(|_| {
+                ParseError::at(value, start_section, ParseErrorKind::InvalidRangeStart)
+            })?,
+            end: end_section
+                .parse::<u32>()
+                .map_err(|_| ParseError::at(value, end_section, ParseErrorKind::InvalidRangeEnd))?,
+        })
+    }
+}
+
+impl Binary for SectionRange {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.start.encode(encoder);
+        self.end.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(Self {
+            start: u32::decode(decoder)?,
+            end: u32::decode(decoder)?,
+        })
+    }
+}
+
+pub struct ElfPair {
+    first_elf_range: SectionRange,
+    second_elf_range: SectionRange,
+}
+
+impl TryFrom<&str> for ElfPair {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (first_elf_range, second_elf_range) = value.split_once(',').ok_or_else(|| {
+            ParseError::new(ParseErrorKind::MissingPairSeparator, Span::end_of(value))
+        })?;
+
+        Ok(Self {
+            first_elf_range: SectionRange::try_from(first_elf_range)
+                .map_err(|error| error.within(value, first_elf_range))?,
+            second_elf_range: SectionRange::try_from(second_elf_range)
+                .map_err(|error| error.within(value, second_elf_range))?,
+        })
+    }
+}
+
+impl Binary for ElfPair {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.first_elf_range.encode(encoder);
+        self.second_elf_range.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(Self {
+            first_elf_range: SectionRange::decode(decoder)?,
+            second_elf_range: SectionRange::decode(decoder)?,
+        })
+    }
+}
+
+impl ElfPair {
+    pub fn has_redundant_range(&self) -> bool {
+        let range_start_comparison = self.first_elf_range.start.cmp(&self.second_elf_range.start);
+        let range_end_comparison = self.first_elf_range.end.cmp(&self.second_elf_range.end);
+
+        matches!(
+            (range_start_comparison, range_end_comparison),
+            (Ordering::Greater, Ordering::Less)
+                | (Ordering::Less, Ordering::Greater)
+                | (Ordering::Greater, Ordering::Equal)
+                | (Ordering::Equal, Ordering::Greater)
+                | (Ordering::Equal, Ordering::Less)
+                | (Ordering::Less, Ordering::Equal)
+                | (Ordering::Equal, Ordering::Equal)
+        )
+    }
+
+    pub fn has_overlapping_ranges(&self) -> bool {
+        if self.first_elf_range.end < self.second_elf_range.start {
+            return false;
+        }
+
+        if self.first_elf_range.start > self.second_elf_range.end {
+            return false;
+        }
+
+        true
+    }
+}
+
+pub struct CampCleanup;
+
+impl Solution for CampCleanup {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "camp_cleanup";
+
+    type Input = Vec<ElfPair>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    type Generator = generator::SectionAssignments;
+
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input, |line| ElfPair::try_from(line))?)
+    }
+
+    fn part_one_with(pairs: &Self::Input, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(pairs
+            .iter()
+            .filter(|pair| pair.has_redundant_range())
+            .count())
+    }
+
+    fn part_two_with(pairs: &Self::Input, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(pairs
+            .iter()
+            .filter(|pair| pair.has_overlapping_ranges())
+            .count())
+    }
+}
+
+impl CachedSolution for CampCleanup {
+    const PARSER_VERSION: u32 = 1;
+}
+
+impl StreamingSolution for CampCleanup {
+    /// The number of pairs with a redundant range and with overlapping ranges.
+    type Summary = (usize, usize);
+
+    fn summarize_stream(reader: &mut dyn BufRead, _: &Self::Config) -> Result<Self::Summary> {
+        let mut counts = (0, 0);
+
+        parse_stream_lines(
+            reader,
+            |line| ElfPair::try_from(line),
+            |pair| {
+                counts.0 += pair.has_redundant_range() as usize;
+                counts.1 += pair.has_overlapping_ranges() as usize;
+            },
+        )?;
+
+        Ok(counts)
+    }
+
+    fn part_one_from_summary(counts: &Self::Summary, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(counts.0)
+    }
+
+    fn part_two_from_summary(counts: &Self::Summary, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(counts.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        cache::round_trip,
+        generator::Generator,
+        normalize::{normalize, variants, NormalizedReader},
+        random::Rng,
+    };
+
+    use crate::generator::SectionAssignments;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn pair(value: &str) -> ElfPair {
+        ElfPair::try_from(value).ok().unwrap()
+    }
+
+    fn parse_error(value: &str) -> ParseError {
+        ElfPair::try_from(value).err().unwrap()
+    }
+
+    #[test]
+    fn parses_section_ranges() {
+        let range = SectionRange::try_from("12-345").ok().unwrap();
+
+        assert_eq!((range.start, range.end), (12, 345));
+    }
+
+    #[test]
+    fn locates_range_errors_inside_the_pair() {
+        let error = parse_error("2-4,6x-8");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidRangeStart);
+        assert_eq!(error.span, Span::new(4, 6));
+
+        let error = parse_error("2-4,6-");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidRangeEnd);
+        assert_eq!(error.span, Span::new(6, 6));
+    }
+
+    #[test]
+    fn rejects_missing_separators() {
+        assert_eq!(
+            parse_error("2-4 6-8").kind,
+            ParseErrorKind::MissingPairSeparator
+        );
+        assert_eq!(
+            parse_error("2-4,68").kind,
+            ParseErrorKind::MissingRangeSeparator
+        );
+    }
+
+    #[test]
+    fn detects_redundant_ranges() {
+        assert!(pair("2-8,3-7").has_redundant_range());
+        assert!(pair("6-6,4-6").has_redundant_range());
+        assert!(pair("3-5,3-5").has_redundant_range());
+        assert!(!pair("5-7,7-9").has_redundant_range());
+    }
+
+    #[test]
+    fn detects_overlapping_ranges() {
+        assert!(pair("5-7,7-9").has_overlapping_ranges());
+        assert!(pair("6-6,4-6").has_overlapping_ranges());
+        assert!(!pair("2-4,6-8").has_overlapping_ranges());
+        assert!(!pair("6-8,2-4").has_overlapping_ranges());
+    }
+
+    #[test]
+    fn streams_the_same_answers_as_the_batch_solver() {
+        let input = SectionAssignments::default().generate(&mut Rng::new(1));
+        let parsed_input = CampCleanup::parse(&input).ok().unwrap();
+
+        assert_eq!(
+            CampCleanup::summarize_stream(&mut input.as_bytes(), &()).unwrap(),
+            (
+                CampCleanup::part_one(&parsed_input).unwrap(),
+                CampCleanup::part_two(&parsed_input).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn solves_the_same_after_a_cache_round_trip() {
+        let input = SectionAssignments::default().generate(&mut Rng::new(1));
+        let parsed_input = CampCleanup::parse(&input).unwrap();
+        let cached_input = round_trip(&parsed_input).unwrap();
+
+        assert_eq!(
+            CampCleanup::part_one(&cached_input).unwrap(),
+            CampCleanup::part_one(&parsed_input).unwrap()
+        );
+        assert_eq!(
+            CampCleanup::part_two(&cached_input).unwrap(),
+            CampCleanup::part_two(&parsed_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = CampCleanup::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(CampCleanup::part_one(&input).unwrap(), 2, "{}", variant);
+            assert_eq!(CampCleanup::part_two(&input).unwrap(), 4, "{}", variant);
+
+            let summary =
+                CampCleanup::summarize_stream(&mut NormalizedReader::new(text.as_bytes()), &())
+                    .unwrap();
+
+            assert_eq!(
+                CampCleanup::part_one_from_summary(&summary, &()).unwrap(),
+                2,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                CampCleanup::part_two_from_summary(&summary, &()).unwrap(),
+                4,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = CampCleanup::parse(EXAMPLE).unwrap();
+
+        assert_eq!(CampCleanup::part_one(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = CampCleanup::parse(EXAMPLE).unwrap();
+
+        assert_eq!(CampCleanup::part_two(&input).unwrap(), 4);
+    }
+}
@@ -0,0 +1,151 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+pub struct CrateRearrangement {
+    pub stacks: usize,
+    pub crates: usize,
+    pub moves: usize,
+    pub max_move_amount: u8,
+}
+
+impl Default for CrateRearrangement {
+    fn default() -> Self {
+        Self {
+            stacks: 9,
+            crates: 50,
+            moves: 500,
+            max_move_amount: 20,
+        }
+    }
+}
+
+impl CrateRearrangement {
+    fn drawing(&self, stack_heights: &[usize], rng: &mut Rng) -> String {
+        let tallest_stack = stack_heights.iter().copied().max().unwrap_or(0);
+
+        let rows = (0..tallest_stack).rev().map(|level| {
+            stack_heights
+                .iter()
+                .map(|&height| match level < height {
+                    true => format!("[{}]", (b'A' + rng.range(0..=25)) as char),
+                    false => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+        let stack_ids = (1..=stack_heights.len())
+            .map(|id| format!("{:^3}", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        rows.chain([stack_ids]).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl Generator for CrateRearrangement {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            crates: default.crates * scale,
+            moves: default.moves * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        // Every stack starts with a crate when there are enough of them, so that the message has a
+        // crate on top of each stack.
+        let mut stack_heights = vec![usize::from(self.crates >= self.stacks); self.stacks];
+
+        for _ in stack_heights.iter().sum::<usize>()..self.crates {
+            stack_heights[rng.range(0..=self.stacks - 1)] += 1;
+        }
+
+        let mut instructions = Vec::with_capacity(self.moves);
+
+        // Moves are simulated so that they always leave a crate on the origin stack. Some stack has
+        // a crate to spare as long as there are more crates than stacks.
+        while instructions.len() < self.moves && self.stacks > 1 && self.crates > self.stacks {
+            let origin = rng.range(0..=self.stacks - 1);
+            let destination = (origin + rng.range(1..=self.stacks - 1)) % self.stacks;
+
+            if stack_heights[origin] <= 1 {
+                continue;
+            }
+
+            let amount =
+                rng.range(1..=(stack_heights[origin] - 1).min(self.max_move_amount as usize));
+
+            stack_heights[origin] -= amount;
+            stack_heights[destination] += amount;
+
+            instructions.push((amount, origin, destination));
+        }
+
+        let initial_stack_heights = instructions.iter().rev().fold(
+            stack_heights,
+            |mut stack_heights, &(amount, origin, destination)| {
+                stack_heights[origin] += amount;
+                stack_heights[destination] -= amount;
+                stack_heights
+            },
+        );
+
+        let instructions = instructions
+            .iter()
+            .map(|(amount, origin, destination)| {
+                format!(
+                    "move {} from {} to {}\n",
+                    amount,
+                    origin + 1,
+                    destination + 1
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "{}\n\n{}",
+            self.drawing(&initial_stack_heights, rng),
+            instructions
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::SupplyStacks;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        for seed in 0..10 {
+            let input = CrateRearrangement::default().generate(&mut Rng::new(seed));
+            let parsed_input = SupplyStacks::parse(&input).unwrap();
+
+            assert_eq!(parsed_input.1.len(), 500);
+            assert!(SupplyStacks::part_one(&parsed_input).is_ok());
+            assert!(SupplyStacks::part_two(&parsed_input).is_ok());
+        }
+    }
+
+    #[test]
+    fn keeps_a_crate_on_top_of_every_stack() {
+        for scale in [1, 10] {
+            for seed in 0..5 {
+                let input = CrateRearrangement::scaled(scale).generate(&mut Rng::new(seed));
+                let parsed_input = SupplyStacks::parse(&input).unwrap();
+
+                assert_eq!(SupplyStacks::part_one(&parsed_input).unwrap().len(), 9);
+                assert_eq!(SupplyStacks::part_two(&parsed_input).unwrap().len(), 9);
+            }
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<SupplyStacks>(5, 200).unwrap();
+    }
+}
@@ -0,0 +1,551 @@
+pub mod generator;
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use aoc_common::{
+    binary::{Binary, Decoder, Encoder},
+    cache::CachedSolution,
+    diagnostic::{self, Span},
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingInstructions,
+    MissingStackIds,
+    InvalidCrate,
+    CrateOutsideStacks,
+    MalformedInstruction,
+    InvalidCrateAmount,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingInstructions => write!(
+                f,
+                "The input should contain the initial stacks configuration and the moving instructions separated by a double newline"
+            ),
+            Self::MissingStackIds => write!(
+                f,
+                "The last line of the stacks drawing should list the stack IDs"
+            ),
+            Self::InvalidCrate => write!(
+                f,
+                "A crate should be drawn as its marker between brackets, like [A]"
+            ),
+            Self::CrateOutsideStacks => {
+                write!(f, "This crate is not above any of the listed stacks")
+            }
+            Self::MalformedInstruction => write!(
+                f,
+                "An instruction should look like \"move <amount> from <stack> to <stack>\""
+            ),
+            Self::InvalidCrateAmount => write!(
+                f,
+                "The amount of crates to move in an instruction should be an integer"
+            ),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub type CrateStackId = String;
+pub type CrateStack = Vec<char>;
+
+#[derive(Debug, Clone)]
+pub struct CrateStacks {
+    state: HashMap<CrateStackId, CrateStack>,
+    order: Vec<CrateStackId>,
+}
+
+fn parse_crate_row(row: &str, number_of_stacks: usize) -> Result<Vec<(usize, char)>, ParseError> {
+    let row_chars = row.char_indices().collect::<Vec<_>>();
+
+    let mut crates = Vec::new();
+
+    for (stack_index, crate_box) in row_chars.chunks(4).enumerate() {
+        let crate_box = &crate_box[..crate_box.len().min(3)];
+
+        let box_span = Span::new(
+            crate_box[0].0,
+            crate_box[crate_box.len() - 1].0 + crate_box[crate_box.len() - 1].1.len_utf8(),
+        );
+
+        let crate_marker = match crate_box {
+            [(_, '['), (_, crate_marker), (_, ']')] if *crate_marker != ' ' => *crate_marker,
+            _ if crate_box.iter().all(|&(_, box_char)| box_char == ' ') => continue,
+            _ => return Err(ParseError::new(ParseErrorKind::InvalidCrate, box_span)),
+        };
+
+        if stack_index >= number_of_stacks {
+            return Err(ParseError::new(
+                ParseErrorKind::CrateOutsideStacks,
+                box_span,
+            ));
+        }
+
+        crates.push((stack_index, crate_marker));
+    }
+
+    Ok(crates)
+}
+
+impl FromStr for CrateStacks {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut lines = value.split('\n').collect::<Vec<_>>();
+
+        let stack_ids_line = lines.pop().unwrap_or_default();
+
+        let stacks_order = stack_ids_line
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        if stacks_order.is_empty() {
+            return Err(ParseError::at(
+                value,
+                stack_ids_line,
+                ParseErrorKind::MissingStackIds,
+            ));
+        }
+
+        let crate_rows = lines
+            .iter()
+            .map(|row| {
+                parse_crate_row(row, stacks_order.len()).map_err(|error| error.within(value, row))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stacks_state = stacks_order
+            .iter()
+            .map(|stack_id| (stack_id.clone(), CrateStack::new()))
+            .collect::<HashMap<_, _>>();
+
+        for (stack_index, crate_marker) in crate_rows.into_iter().rev().flatten() {
+            stacks_state
+                .entry(stacks_order[stack_index].clone())
+                .or_default()
+                .push(crate_marker);
+        }
+
+        Ok(Self {
+            order: stacks_order,
+            state: stacks_state,
+        })
+    }
+}
+
+impl Binary for CrateStacks {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.state.encode(encoder);
+        self.order.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        let state = HashMap::<CrateStackId, CrateStack>::decode(decoder)?;
+        let order = Vec::<CrateStackId>::decode(decoder)?;
+
+        if let Some(stack_id) = order.iter().find(|stack_id| !state.contains_key(*stack_id)) {
+            return Err(format!("Stack {} is ordered but has no crates", stack_id).into());
+        }
+
+        Ok(Self { state, order })
+    }
+}
+
+impl CrateStacks {
+    pub fn get_target_crate_stacks(
+        &self,
+        instruction: &MoveInstruction,
+    ) -> Result<(CrateStack, CrateStack)> {
+        let origin_stack = self
+            .state
+            .get(&instruction.origin_stack_id)
+            .ok_or(format!(
+                "Tried to move crate from stack {} to stack {} but the origin stack does not exist",
+                instruction.origin_stack_id, instruction.destination_stack_id
+            ))?
+            .clone();
+
+        let destination_stack = self
+            .state
+            .get(&instruction.destination_stack_id)
+            .ok_or(format!(
+                "Tried to move crate from stack {} to stack {} but the destination stack does not exist",
+                instruction.origin_stack_id, instruction.destination_stack_id
+            ))?
+            .clone();
+
+        Ok((origin_stack, destination_stack))
+    }
+
+    pub fn move_crates_with_cratemover_9000(&self, instruction: &MoveInstruction) -> Result<Self> {
+        let (mut origin_stack, mut destination_stack) =
+            self.get_target_crate_stacks(instruction)?;
+
+        for _ in 0..instruction.amount {
+            let crate_to_move = origin_stack.pop().ok_or(format!(
+                "Tried to move crate from stack {}, but it has no crates",
+                instruction.origin_stack_id
+            ))?;
+
+            destination_stack.push(crate_to_move);
+        }
+
+        let mut stacks_state = self.state.clone();
+
+        stacks_state.insert(instruction.origin_stack_id.clone(), origin_stack);
+        stacks_state.insert(instruction.destination_stack_id.clone(), destination_stack);
+
+        Ok(CrateStacks {
+            state: stacks_state,
+            order: self.order.clone(),
+        })
+    }
+
+    pub fn move_crates_with_cratemover_9001(&self, instruction: &MoveInstruction) -> Result<Self> {
+        let (mut origin_stack, mut destination_stack) =
+            self.get_target_crate_stacks(instruction)?;
+
+        let remaining_crates = origin_stack
+            .len()
+            .checked_sub(instruction.amount as usize)
+            .ok_or(format!(
+                "Tried to move {} crates from stack {}, but it only has {}",
+                instruction.amount,
+                instruction.origin_stack_id,
+                origin_stack.len()
+            ))?;
+
+        let crates_to_move = origin_stack.split_off(remaining_crates);
+
+        destination_stack.extend_from_slice(&crates_to_move[..]);
+
+        let mut stacks_state = self.state.clone();
+
+        stacks_state.insert(instruction.origin_stack_id.clone(), origin_stack);
+        stacks_state.insert(instruction.destination_stack_id.clone(), destination_stack);
+
+        Ok(CrateStacks {
+            state: stacks_state,
+            order: self.order.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveInstruction {
+    amount: u8,
+    origin_stack_id: CrateStackId,
+    destination_stack_id: CrateStackId,
+}
+
+impl FromStr for MoveInstruction {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split(' ').collect::<Vec<_>>()[..] {
+            ["move", amount, "from", origin_stack_id, "to", destination_stack_id] => Ok(Self {
+                amount: amount.parse::<u8>().map_err(|_| {
+                    ParseError::at(value, amount, ParseErrorKind::InvalidCrateAmount)
+                })?,
+                origin_stack_id: origin_stack_id.to_string(),
+                destination_stack_id: destination_stack_id.to_string(),
+            }),
+            _ => Err(ParseError::at(
+                value,
+                value,
+                ParseErrorKind::MalformedInstruction,
+            )),
+        }
+    }
+}
+
+impl Binary for MoveInstruction {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.amount.encode(encoder);
+        self.origin_stack_id.encode(encoder);
+        self.destination_stack_id.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(Self {
+            amount: u8::decode(decoder)?,
+            origin_stack_id: CrateStackId::decode(decoder)?,
+            destination_stack_id: CrateStackId::decode(decoder)?,
+        })
+    }
+}
+
+pub fn get_message_from_crate_stacks(crate_stacks: &CrateStacks) -> String {
+    crate_stacks
+        .order
+        .iter()
+        .map(|stack_id| {
+            crate_stacks
+                .state
+                .get(stack_id)
+                .expect(
+                    "Crate stacks order vector should only contain existing stack IDs as elements",
+                )
+                .iter()
+                .last()
+        })
+        .collect::<Option<String>>()
+        .unwrap_or_default()
+}
+
+pub struct SupplyStacks;
+
+impl Solution for SupplyStacks {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "supply_stacks";
+
+    type Input = (CrateStacks, Vec<MoveInstruction>);
+    type PartOne = String;
+    type PartTwo = String;
+
+    type Generator = generator::CrateRearrangement;
+
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input = input.trim_end();
+
+        let (initial_stacks, all_instructions) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::new(ParseErrorKind::MissingInstructions, Span::end_of(input)).locate(input)
+        })?;
+
+        let crate_stacks = initial_stacks
+            .parse::<CrateStacks>()
+            .map_err(|error| error.within(input, initial_stacks).locate(input))?;
+
+        let instructions = all_instructions
+            .split('\n')
+            .map(|line| {
+                line.parse::<MoveInstruction>()
+                    .map_err(|error| error.within(input, line).locate(input))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((crate_stacks, instructions))
+    }
+
+    fn part_one_with(
+        (crate_stacks, instructions): &Self::Input,
+        _: &Self::Config,
+    ) -> Result<Self::PartOne> {
+        let cratemover_9000_rearranged_stacks = instructions
+            .iter()
+            .try_fold(crate_stacks.clone(), |stacks, instruction| {
+                stacks.move_crates_with_cratemover_9000(instruction)
+            })?;
+
+        Ok(get_message_from_crate_stacks(
+            &cratemover_9000_rearranged_stacks,
+        ))
+    }
+
+    fn part_two_with(
+        (crate_stacks, instructions): &Self::Input,
+        _: &Self::Config,
+    ) -> Result<Self::PartTwo> {
+        let cratemover_9001_rearranged_stacks = instructions
+            .iter()
+            .try_fold(crate_stacks.clone(), |stacks, instruction| {
+                stacks.move_crates_with_cratemover_9001(instruction)
+            })?;
+
+        Ok(get_message_from_crate_stacks(
+            &cratemover_9001_rearranged_stacks,
+        ))
+    }
+}
+
+impl CachedSolution for SupplyStacks {
+    const PARSER_VERSION: u32 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        cache::round_trip,
+        diagnostic::LocatedError,
+        normalize::{normalize, variants},
+    };
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn stacks(crate_stacks: &CrateStacks) -> Vec<String> {
+        crate_stacks
+            .order
+            .iter()
+            .map(|stack_id| crate_stacks.state[stack_id].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_crate_stacks_bottom_up() {
+        let crate_stacks = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+            .parse::<CrateStacks>()
+            .unwrap();
+
+        assert_eq!(crate_stacks.order, vec!["1", "2", "3"]);
+        assert_eq!(stacks(&crate_stacks), vec!["ZN", "MCD", "P"]);
+    }
+
+    #[test]
+    fn parses_crate_rows_without_trailing_spaces() {
+        let crate_stacks = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3"
+            .parse::<CrateStacks>()
+            .unwrap();
+
+        assert_eq!(stacks(&crate_stacks), vec!["ZN", "MCD", "P"]);
+    }
+
+    #[test]
+    fn rejects_malformed_crates() {
+        let error = "[A] [B\n 1   2".parse::<CrateStacks>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCrate);
+        assert_eq!(error.span, Span::new(4, 6));
+    }
+
+    #[test]
+    fn rejects_crates_outside_the_stacks() {
+        let error = "[A] [B]\n 1".parse::<CrateStacks>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::CrateOutsideStacks);
+        assert_eq!(error.span, Span::new(4, 7));
+    }
+
+    #[test]
+    fn rejects_missing_stack_ids() {
+        let error = "[A]\n".parse::<CrateStacks>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::MissingStackIds);
+    }
+
+    #[test]
+    fn parses_move_instructions() {
+        let instruction = "move 12 from 1 to 3".parse::<MoveInstruction>().unwrap();
+
+        assert_eq!(instruction.amount, 12);
+        assert_eq!(instruction.origin_stack_id, "1");
+        assert_eq!(instruction.destination_stack_id, "3");
+    }
+
+    #[test]
+    fn rejects_malformed_move_instructions() {
+        let error = "move x from 1 to 3".parse::<MoveInstruction>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCrateAmount);
+        assert_eq!(error.span, Span::new(5, 6));
+
+        let error = "move 1 from 1".parse::<MoveInstruction>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::MalformedInstruction);
+    }
+
+    #[test]
+    fn locates_instruction_errors_in_the_input() {
+        let error = SupplyStacks::parse(&EXAMPLE.replace("move 2 from 2", "move 2 form 2"))
+            .unwrap_err()
+            .downcast::<LocatedError<ParseErrorKind>>()
+            .unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::MalformedInstruction);
+        assert_eq!((error.line, error.column), (8, 1));
+    }
+
+    #[test]
+    fn requires_instructions() {
+        let error = SupplyStacks::parse("[A]\n 1\n")
+            .unwrap_err()
+            .downcast::<LocatedError<ParseErrorKind>>()
+            .unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::MissingInstructions);
+    }
+
+    #[test]
+    fn moves_crates_one_at_a_time_with_the_9000() {
+        let (crate_stacks, instructions) = SupplyStacks::parse(EXAMPLE).unwrap();
+
+        let moved = instructions[..2]
+            .iter()
+            .try_fold(crate_stacks, |stacks, instruction| {
+                stacks.move_crates_with_cratemover_9000(instruction)
+            })
+            .unwrap();
+
+        assert_eq!(stacks(&moved), vec!["", "MC", "PDNZ"]);
+    }
+
+    #[test]
+    fn moves_crates_at_once_with_the_9001() {
+        let (crate_stacks, instructions) = SupplyStacks::parse(EXAMPLE).unwrap();
+
+        let moved = instructions[..2]
+            .iter()
+            .try_fold(crate_stacks, |stacks, instruction| {
+                stacks.move_crates_with_cratemover_9001(instruction)
+            })
+            .unwrap();
+
+        assert_eq!(stacks(&moved), vec!["", "MC", "PZND"]);
+    }
+
+    #[test]
+    fn keeps_stacks_and_instructions_through_a_cache_round_trip() {
+        let (crate_stacks, instructions) =
+            round_trip(&SupplyStacks::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(crate_stacks.order, vec!["1", "2", "3"]);
+        assert_eq!(stacks(&crate_stacks), vec!["ZN", "MCD", "P"]);
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[3].amount, 1);
+        assert_eq!(instructions[3].origin_stack_id, "1");
+        assert_eq!(instructions[3].destination_stack_id, "2");
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = SupplyStacks::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                SupplyStacks::part_one(&input).unwrap(),
+                "CMZ",
+                "{}",
+                variant
+            );
+            assert_eq!(
+                SupplyStacks::part_two(&input).unwrap(),
+                "MCD",
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = SupplyStacks::parse(EXAMPLE).unwrap();
+
+        assert_eq!(SupplyStacks::part_one(&input).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = SupplyStacks::parse(EXAMPLE).unwrap();
+
+        assert_eq!(SupplyStacks::part_two(&input).unwrap(), "MCD");
+    }
+}
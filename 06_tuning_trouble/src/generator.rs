@@ -0,0 +1,73 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+/// A datastream drawn from only three letters, so the only markers are the ones planted in it.
+pub struct Datastream {
+    pub length: usize,
+}
+
+impl Default for Datastream {
+    fn default() -> Self {
+        Self { length: 4096 }
+    }
+}
+
+impl Datastream {
+    fn plant_marker(datastream: &mut [char], rng: &mut Rng, start: usize, size: usize) {
+        let mut letters = ('a'..='z').collect::<Vec<_>>();
+        rng.shuffle(&mut letters);
+
+        datastream[start..start + size].copy_from_slice(&letters[..size]);
+    }
+}
+
+impl Generator for Datastream {
+    fn scaled(scale: usize) -> Self {
+        Self {
+            length: Self::default().length * scale,
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let length = self.length.max(14);
+
+        let mut datastream = (0..length)
+            .map(|_| *rng.choose(&['x', 'y', 'z']))
+            .collect::<Vec<_>>();
+
+        let message_marker_start = rng.range((length - 14) / 2..=length - 14);
+        let packet_marker_start = rng.range(0..=message_marker_start / 2);
+
+        Self::plant_marker(&mut datastream, rng, packet_marker_start, 4);
+        Self::plant_marker(&mut datastream, rng, message_marker_start, 14);
+
+        datastream.into_iter().chain(['\n']).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::TuningTrouble;
+
+    #[test]
+    fn generates_datastreams_with_both_markers() {
+        for seed in 0..10 {
+            let input = Datastream::default().generate(&mut Rng::new(seed));
+            let datastream = TuningTrouble::parse(&input).unwrap();
+
+            assert_eq!(datastream.len(), 4096);
+
+            let packet_marker_end = TuningTrouble::part_one(&datastream).unwrap();
+            let message_marker_end = TuningTrouble::part_two(&datastream).unwrap();
+
+            assert!(packet_marker_end < message_marker_end);
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<TuningTrouble>(6, 200).unwrap();
+    }
+}
@@ -0,0 +1,554 @@
+pub mod generator;
+
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    io::BufRead,
+    slice::Windows,
+};
+
+use aoc_common::{
+    config::{self, integer_at_least, unknown_key},
+    diagnostic::{self, LocatedError, Span},
+    stream::StreamingSolution,
+    toml::Value,
+    variants::{SolutionVariants, Solver},
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(signal_char) => write!(
+                f,
+                "The datastream should only contain lowercase letters. Got: {:?}",
+                signal_char
+            ),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub fn find_first_marker_start_index(
+    marker_windows: Windows<'_, char>,
+    marker_size: usize,
+) -> Option<usize> {
+    marker_windows
+        .enumerate()
+        .map(|(index, window)| (index + marker_size, window))
+        .find(|(_, window)| HashSet::<&char>::from_iter(window.iter()).len() == marker_size)
+        .map(|(index, _)| index)
+}
+
+/// Looks for a marker in a datastream fed one lowercase letter at a time, counting the letters in
+/// the last `marker_size` ones instead of keeping the whole datastream.
+pub struct MarkerScanner {
+    marker_size: usize,
+    window: VecDeque<u8>,
+    letter_counts: [usize; 26],
+    repeated_letters: usize,
+    scanned: usize,
+    marker_end: Option<usize>,
+}
+
+impl MarkerScanner {
+    pub fn new(marker_size: usize) -> Self {
+        Self {
+            marker_size,
+            window: VecDeque::with_capacity(marker_size + 1),
+            letter_counts: [0; 26],
+            repeated_letters: 0,
+            scanned: 0,
+            marker_end: None,
+        }
+    }
+
+    pub fn push(&mut self, letter: u8) {
+        if self.marker_end.is_some() {
+            return;
+        }
+
+        self.scanned += 1;
+        self.window.push_back(letter);
+
+        let letter_count = &mut self.letter_counts[(letter - b'a') as usize];
+        *letter_count += 1;
+
+        if *letter_count == 2 {
+            self.repeated_letters += 1;
+        }
+
+        if self.window.len() > self.marker_size {
+            let oldest_letter = self.window.pop_front().unwrap();
+            let letter_count = &mut self.letter_counts[(oldest_letter - b'a') as usize];
+
+            if *letter_count == 2 {
+                self.repeated_letters -= 1;
+            }
+
+            *letter_count -= 1;
+        }
+
+        if self.window.len() == self.marker_size && self.repeated_letters == 0 {
+            self.marker_end = Some(self.scanned);
+        }
+    }
+
+    /// How many characters had been read when the marker was complete.
+    pub fn marker_end(&self) -> Option<usize> {
+        self.marker_end
+    }
+}
+
+/// Same as `find_first_marker_start_index`, but slides a single window along the datastream
+/// instead of hashing every window from scratch. The datastream should only contain lowercase
+/// letters, like parsed ones do.
+pub fn scan_marker_end(datastream: &[char], marker_size: usize) -> Option<usize> {
+    let mut scanner = MarkerScanner::new(marker_size);
+
+    datastream.iter().find_map(|&signal_char| {
+        scanner.push(signal_char as u8);
+        scanner.marker_end()
+    })
+}
+
+const EXCERPT_LENGTH: usize = 40;
+
+fn stream_error(
+    signal_char: char,
+    index: usize,
+    excerpt: &VecDeque<u8>,
+) -> LocatedError<ParseErrorKind> {
+    let mut source_line = excerpt.iter().map(|&byte| byte as char).collect::<String>();
+    let mut snippet = String::new();
+
+    if signal_char != '\n' {
+        source_line.push(signal_char);
+        snippet.push(signal_char);
+    }
+
+    LocatedError {
+        kind: ParseErrorKind::InvalidCharacter(signal_char),
+        line: 1,
+        column: index + 1,
+        snippet,
+        source_line,
+        source_column: index + 1 - excerpt.len(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub packet_marker_size: usize,
+    pub message_marker_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            packet_marker_size: 4,
+            message_marker_size: 14,
+        }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &["packet_marker_size", "message_marker_size"];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "packet_marker_size" => self.packet_marker_size = integer_at_least(key, value, 1)?,
+            "message_marker_size" => self.message_marker_size = integer_at_least(key, value, 1)?,
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct TuningTrouble;
+
+impl Solution for TuningTrouble {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "tuning_trouble";
+
+    type Input = Vec<char>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    type Generator = generator::Datastream;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let datastream = input.trim_end();
+
+        if let Some((index, signal_char)) = datastream
+            .char_indices()
+            .find(|(_, signal_char)| !signal_char.is_ascii_lowercase())
+        {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidCharacter(signal_char),
+                Span::new(index, index + signal_char.len_utf8()),
+            )
+            .locate(input)
+            .into());
+        }
+
+        Ok(datastream.chars().collect())
+    }
+
+    fn part_one_with(buffer_chars: &Self::Input, config: &Self::Config) -> Result<Self::PartOne> {
+        let packet_marker_size = config.packet_marker_size;
+
+        let packet_marker_windows = buffer_chars.windows(packet_marker_size);
+
+        Ok(
+            find_first_marker_start_index(packet_marker_windows, packet_marker_size)
+                .ok_or("Could not find a start-of-packet sequence")?,
+        )
+    }
+
+    fn part_two_with(buffer_chars: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        let message_marker_size = config.message_marker_size;
+
+        let message_marker_windows = buffer_chars.windows(message_marker_size);
+
+        Ok(
+            find_first_marker_start_index(message_marker_windows, message_marker_size)
+                .ok_or("Could not find a start-of-message sequence")?,
+        )
+    }
+}
+
+impl SolutionVariants for TuningTrouble {
+    fn part_one_solvers() -> Vec<Solver<Self, Self::PartOne>> {
+        vec![
+            Solver {
+                name: "hash set window",
+                solve: Self::part_one_with,
+            },
+            Solver {
+                name: "counting array",
+                solve: |buffer_chars, config| {
+                    Ok(scan_marker_end(buffer_chars, config.packet_marker_size)
+                        .ok_or("Could not find a start-of-packet sequence")?)
+                },
+            },
+        ]
+    }
+
+    fn part_two_solvers() -> Vec<Solver<Self, Self::PartTwo>> {
+        vec![
+            Solver {
+                name: "hash set window",
+                solve: Self::part_two_with,
+            },
+            Solver {
+                name: "counting array",
+                solve: |buffer_chars, config| {
+                    Ok(scan_marker_end(buffer_chars, config.message_marker_size)
+                        .ok_or("Could not find a start-of-message sequence")?)
+                },
+            },
+        ]
+    }
+}
+
+impl StreamingSolution for TuningTrouble {
+    /// Where the start-of-packet and start-of-message markers end.
+    type Summary = (Option<usize>, Option<usize>);
+
+    fn summarize_stream(reader: &mut dyn BufRead, config: &Self::Config) -> Result<Self::Summary> {
+        let mut packet_marker_scanner = MarkerScanner::new(config.packet_marker_size);
+        let mut message_marker_scanner = MarkerScanner::new(config.message_marker_size);
+
+        let mut index = 0;
+        let mut excerpt = VecDeque::with_capacity(EXCERPT_LENGTH + 1);
+        let mut trailing_whitespace = None;
+
+        loop {
+            let chunk = reader
+                .fill_buf()
+                .map_err(|error| format!("Could not read the datastream: {}", error))?;
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            for (chunk_index, &byte) in chunk.iter().enumerate() {
+                if byte.is_ascii_whitespace() {
+                    trailing_whitespace.get_or_insert((index, byte as char));
+                    index += 1;
+
+                    continue;
+                }
+
+                // Whitespace is only allowed at the end, like `parse` trims it.
+                if let Some((whitespace_index, whitespace)) = trailing_whitespace {
+                    return Err(stream_error(whitespace, whitespace_index, &excerpt).into());
+                }
+
+                if !byte.is_ascii_lowercase() {
+                    let signal_char = String::from_utf8_lossy(&chunk[chunk_index..])
+                        .chars()
+                        .next()
+                        .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+                    return Err(stream_error(signal_char, index, &excerpt).into());
+                }
+
+                packet_marker_scanner.push(byte);
+                message_marker_scanner.push(byte);
+
+                excerpt.push_back(byte);
+
+                if excerpt.len() > EXCERPT_LENGTH {
+                    excerpt.pop_front();
+                }
+
+                index += 1;
+            }
+
+            let chunk_length = chunk.len();
+            reader.consume(chunk_length);
+        }
+
+        Ok((
+            packet_marker_scanner.marker_end(),
+            message_marker_scanner.marker_end(),
+        ))
+    }
+
+    fn part_one_from_summary(
+        marker_ends: &Self::Summary,
+        _: &Self::Config,
+    ) -> Result<Self::PartOne> {
+        Ok(marker_ends
+            .0
+            .ok_or("Could not find a start-of-packet sequence")?)
+    }
+
+    fn part_two_from_summary(
+        marker_ends: &Self::Summary,
+        _: &Self::Config,
+    ) -> Result<Self::PartTwo> {
+        Ok(marker_ends
+            .1
+            .ok_or("Could not find a start-of-message sequence")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        generator::Generator,
+        normalize::{normalize, variants, NormalizedReader},
+        random::Rng,
+        variants::cross_check,
+    };
+
+    use crate::generator::Datastream;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    const MORE_EXAMPLES: [(&str, usize, usize); 4] = [
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn parses_the_datastream_without_trailing_newline() {
+        assert_eq!(
+            TuningTrouble::parse("abcd\n").unwrap(),
+            vec!['a', 'b', 'c', 'd']
+        );
+    }
+
+    #[test]
+    fn rejects_non_lowercase_characters() {
+        let error = TuningTrouble::parse("abcD")
+            .unwrap_err()
+            .downcast::<LocatedError<ParseErrorKind>>()
+            .unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('D'));
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+
+    #[test]
+    fn finds_markers_right_after_their_last_character() {
+        let datastream = "aabcd".chars().collect::<Vec<_>>();
+
+        assert_eq!(
+            find_first_marker_start_index(datastream.windows(4), 4),
+            Some(5)
+        );
+        assert_eq!(
+            find_first_marker_start_index(datastream.windows(5), 5),
+            None
+        );
+    }
+
+    #[test]
+    fn scans_markers_as_letters_come_in() {
+        let mut scanner = MarkerScanner::new(4);
+
+        for letter in "aabcbdef".bytes() {
+            scanner.push(letter);
+        }
+
+        assert_eq!(scanner.marker_end(), Some(7));
+    }
+
+    #[test]
+    fn counts_the_same_markers_as_the_hash_set_windows() {
+        let inputs = [EXAMPLE.to_string(), "aaaa".to_string(), "abc".to_string()]
+            .into_iter()
+            .chain(MORE_EXAMPLES.map(|(datastream, _, _)| datastream.to_string()))
+            .chain((1..=3).map(|seed| Datastream::default().generate(&mut Rng::new(seed))));
+
+        for input in inputs {
+            let parsed_input = TuningTrouble::parse(&input).unwrap();
+
+            for marker_size in [1, 2, 3, 4, 14] {
+                let config = Config {
+                    packet_marker_size: marker_size,
+                    message_marker_size: marker_size + 1,
+                };
+
+                cross_check::<TuningTrouble>(&parsed_input, &config).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn streams_the_same_answers_as_the_batch_solver() {
+        for (datastream, packet_marker_end, message_marker_end) in MORE_EXAMPLES {
+            assert_eq!(
+                TuningTrouble::summarize_stream(&mut datastream.as_bytes(), &Config::default())
+                    .unwrap(),
+                (Some(packet_marker_end), Some(message_marker_end))
+            );
+        }
+
+        let input = Datastream::default().generate(&mut Rng::new(1));
+        let parsed_input = TuningTrouble::parse(&input).unwrap();
+
+        assert_eq!(
+            TuningTrouble::summarize_stream(&mut input.as_bytes(), &Config::default()).unwrap(),
+            (
+                Some(TuningTrouble::part_one(&parsed_input).unwrap()),
+                Some(TuningTrouble::part_two(&parsed_input).unwrap())
+            )
+        );
+    }
+
+    #[test]
+    fn locates_invalid_characters_in_streams() {
+        let locate = |datastream: &str| {
+            TuningTrouble::summarize_stream(&mut datastream.as_bytes(), &Config::default())
+                .unwrap_err()
+                .downcast::<LocatedError<ParseErrorKind>>()
+                .unwrap()
+        };
+
+        let error = locate(&format!("{}Dabc", "x".repeat(50)));
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('D'));
+        assert_eq!(error.column, 51);
+        assert!(error.to_string().ends_with(&format!("{}^", " ".repeat(40))));
+
+        let error = locate("abc de\n");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter(' '));
+        assert_eq!((error.column, error.source_line.as_str()), (4, "abc "));
+        assert!(
+            TuningTrouble::summarize_stream(&mut "abcd\r\n\n".as_bytes(), &Config::default())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn finds_markers_of_configured_sizes() {
+        let input = TuningTrouble::parse(EXAMPLE).unwrap();
+        let config = Config {
+            packet_marker_size: 3,
+            message_marker_size: 5,
+        };
+
+        let marker_ends = (
+            TuningTrouble::part_one_with(&input, &config).unwrap(),
+            TuningTrouble::part_two_with(&input, &config).unwrap(),
+        );
+
+        assert_eq!(
+            TuningTrouble::summarize_stream(&mut EXAMPLE.as_bytes(), &config).unwrap(),
+            (Some(marker_ends.0), Some(marker_ends.1))
+        );
+        assert_eq!(marker_ends, (3, 8));
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = TuningTrouble::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(TuningTrouble::part_one(&input).unwrap(), 7, "{}", variant);
+            assert_eq!(TuningTrouble::part_two(&input).unwrap(), 19, "{}", variant);
+
+            let summary = TuningTrouble::summarize_stream(
+                &mut NormalizedReader::new(text.as_bytes()),
+                &Config::default(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                TuningTrouble::part_one_from_summary(&summary, &Config::default()).unwrap(),
+                7,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                TuningTrouble::part_two_from_summary(&summary, &Config::default()).unwrap(),
+                19,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = TuningTrouble::parse(EXAMPLE).unwrap();
+
+        assert_eq!(TuningTrouble::part_one(&input).unwrap(), 7);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = TuningTrouble::parse(EXAMPLE).unwrap();
+
+        assert_eq!(TuningTrouble::part_two(&input).unwrap(), 19);
+    }
+
+    #[test]
+    fn solves_the_other_examples() {
+        for (datastream, packet_marker_end, message_marker_end) in MORE_EXAMPLES {
+            let input = TuningTrouble::parse(datastream).unwrap();
+
+            assert_eq!(TuningTrouble::part_one(&input).unwrap(), packet_marker_end);
+            assert_eq!(TuningTrouble::part_two(&input).unwrap(), message_marker_end);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{generator::Generator, random::Rng};
+
+const NAME_LETTERS: [char; 8] = ['a', 'b', 'c', 'd', 'f', 'g', 'h', 'j'];
+
+/// A `cd`/`ls` transcript exploring a random directory tree depth first, with file sizes
+/// spread so that the disk is between 60% and 95% full.
+pub struct ShellTranscript {
+    pub directories: usize,
+    pub files_per_directory: RangeInclusive<usize>,
+    pub max_depth: usize,
+}
+
+impl Default for ShellTranscript {
+    fn default() -> Self {
+        Self {
+            directories: 180,
+            files_per_directory: 0..=5,
+            max_depth: 10,
+        }
+    }
+}
+
+struct Directory {
+    depth: usize,
+    subdirectories: Vec<usize>,
+    files: usize,
+}
+
+impl ShellTranscript {
+    fn name(rng: &mut Rng, index: usize) -> String {
+        let letters = (0..rng.range(1..=6))
+            .map(|_| *rng.choose(&NAME_LETTERS))
+            .collect::<String>();
+
+        // The index keeps names unique inside their parent directory.
+        format!("{}{}", letters, index)
+    }
+
+    fn transcript(
+        &self,
+        rng: &mut Rng,
+        directories: &[Directory],
+        file_sizes: &mut impl Iterator<Item = usize>,
+        directory: usize,
+        lines: &mut Vec<String>,
+    ) {
+        lines.push("$ ls".to_string());
+
+        let subdirectory_names = directories[directory]
+            .subdirectories
+            .iter()
+            .enumerate()
+            .map(|(index, _)| Self::name(rng, index))
+            .collect::<Vec<_>>();
+
+        for name in subdirectory_names.iter() {
+            lines.push(format!("dir {}", name));
+        }
+
+        for index in 0..directories[directory].files {
+            let extension = match rng.chance(0.5) {
+                true => format!(".{}", Self::name(rng, 0)),
+                false => String::new(),
+            };
+
+            lines.push(format!(
+                "{} {}f{}",
+                file_sizes.next().unwrap_or(1),
+                Self::name(rng, index),
+                extension
+            ));
+        }
+
+        for (name, &subdirectory) in subdirectory_names
+            .iter()
+            .zip(directories[directory].subdirectories.iter())
+        {
+            lines.push(format!("$ cd {}", name));
+            self.transcript(rng, directories, file_sizes, subdirectory, lines);
+            lines.push("$ cd ..".to_string());
+        }
+    }
+}
+
+impl Generator for ShellTranscript {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            directories: default.directories * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let mut directories = vec![Directory {
+            depth: 0,
+            subdirectories: vec![],
+            files: rng.range(self.files_per_directory.clone()),
+        }];
+
+        while directories.len() < self.directories.max(1) {
+            let parent = rng.range(0..=directories.len() - 1);
+
+            if directories[parent].depth >= self.max_depth {
+                continue;
+            }
+
+            directories.push(Directory {
+                depth: directories[parent].depth + 1,
+                subdirectories: vec![],
+                files: rng.range(self.files_per_directory.clone()),
+            });
+
+            let child = directories.len() - 1;
+            directories[parent].subdirectories.push(child);
+        }
+
+        let file_count = directories
+            .iter()
+            .map(|directory| directory.files)
+            .sum::<usize>();
+        let used_space = rng.range(42_000_000..=66_500_000);
+
+        let weights = (0..file_count)
+            .map(|_| rng.range(1..=1000))
+            .collect::<Vec<usize>>();
+        let total_weight = weights.iter().sum::<usize>().max(1);
+
+        let mut file_sizes = weights
+            .into_iter()
+            .map(|weight| (used_space * weight / total_weight).max(1));
+
+        let mut lines = vec!["$ cd /".to_string()];
+
+        self.transcript(rng, &directories, &mut file_sizes, 0, &mut lines);
+
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::NoSpaceLeftOnDevice;
+
+    #[test]
+    fn generates_consistent_transcripts() {
+        for seed in 0..10 {
+            let input = ShellTranscript::default().generate(&mut Rng::new(seed));
+            let file_system = NoSpaceLeftOnDevice::parse(&input).unwrap();
+
+            assert_eq!(file_system.directories().len(), 180);
+            assert!(NoSpaceLeftOnDevice::part_one(&file_system).is_ok());
+            assert!(NoSpaceLeftOnDevice::part_two(&file_system).is_ok());
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<NoSpaceLeftOnDevice>(7, 200).unwrap();
+    }
+}
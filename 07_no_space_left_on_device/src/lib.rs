@@ -0,0 +1,635 @@
+pub mod generator;
+
+use std::{collections::HashMap, fmt, path::PathBuf, str::FromStr};
+
+use aoc_common::{
+    binary::{Binary, Decoder, Encoder},
+    cache::CachedSolution,
+    config::{self, integer_at_least, unknown_key},
+    diagnostic::{self, Span},
+    toml::Value,
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingCommandPrompt,
+    UnknownCommand,
+    MissingDirectoryName,
+    MalformedListingEntry,
+    InvalidFileSize,
+    ChangeDirectoryIntoFile,
+    ChangeDirectoryIntoMissingDirectory,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCommandPrompt => {
+                write!(f, "The terminal output should start with a \"$ \" command")
+            }
+            Self::UnknownCommand => write!(f, "A command should be either cd or ls"),
+            Self::MissingDirectoryName => {
+                write!(f, "The cd command should contain a directory name after it")
+            }
+            Self::MalformedListingEntry => write!(
+                f,
+                "A listed entry should be \"dir <name>\" or \"<size> <name>\""
+            ),
+            Self::InvalidFileSize => write!(f, "A file size should be an integer"),
+            Self::ChangeDirectoryIntoFile => write!(f, "Tried to cd into a file"),
+            Self::ChangeDirectoryIntoMissingDirectory => {
+                write!(f, "Tried to cd into a non-existent directory")
+            }
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub enum ListedEntry {
+    Directory(String),
+    File(String, usize),
+}
+
+impl FromStr for ListedEntry {
+    type Err = ParseError;
+
+    fn from_str(entry_str: &str) -> Result<Self, Self::Err> {
+        match entry_str.split_once(' ') {
+            Some(("dir", name)) => Ok(Self::Directory(name.to_string())),
+            Some((size, name)) => Ok(Self::File(
+                name.to_string(),
+                size.parse::<usize>().map_err(|_| {
+                    ParseError::at(entry_str, size, ParseErrorKind::InvalidFileSize)
+                })?,
+            )),
+            None => Err(ParseError::at(
+                entry_str,
+                entry_str,
+                ParseErrorKind::MalformedListingEntry,
+            )),
+        }
+    }
+}
+
+pub enum Command {
+    ChangeDirectory(String),
+    List(Vec<ListedEntry>),
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(command_str: &str) -> Result<Self, Self::Err> {
+        let (command_line, output) = command_str
+            .split_once('\n')
+            .map_or((command_str, None), |(command_line, output)| {
+                (command_line, Some(output))
+            });
+
+        match command_line.split_once(' ') {
+            Some(("cd", directory_name)) => Ok(Self::ChangeDirectory(directory_name.to_string())),
+            None if command_line == "cd" => Err(ParseError::new(
+                ParseErrorKind::MissingDirectoryName,
+                Span::end_of(command_line),
+            )),
+            None if command_line == "ls" => {
+                let listed_entries = match output {
+                    None => Vec::new(),
+                    Some(output) => output
+                        .split('\n')
+                        .map(|entry| {
+                            entry
+                                .parse::<ListedEntry>()
+                                .map_err(|error| error.within(command_str, entry))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+
+                Ok(Self::List(listed_entries))
+            }
+            _ => Err(ParseError::at(
+                command_str,
+                command_line,
+                ParseErrorKind::UnknownCommand,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemEntry {
+    name: String,
+    size: usize,
+    kind: FileSystemEntryKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum FileSystemEntryKind {
+    #[default]
+    Directory,
+    File,
+}
+
+impl FileSystemEntry {
+    pub fn new_directory(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            size: 0,
+            kind: FileSystemEntryKind::Directory,
+        }
+    }
+
+    pub fn new_file(name: impl ToString, size: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            size,
+            kind: FileSystemEntryKind::File,
+        }
+    }
+
+    pub fn is_directory(&self) -> bool {
+        match self.kind {
+            FileSystemEntryKind::File => false,
+            FileSystemEntryKind::Directory => true,
+        }
+    }
+}
+
+impl Binary for FileSystemEntryKind {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Directory => encoder.write_u8(0),
+            Self::File => encoder.write_u8(1),
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        match decoder.read_u8()? {
+            0 => Ok(Self::Directory),
+            1 => Ok(Self::File),
+            tag => Err(format!("{} is not an encoded entry kind", tag).into()),
+        }
+    }
+}
+
+impl Binary for FileSystemEntry {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.name.encode(encoder);
+        self.size.encode(encoder);
+        self.kind.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(Self {
+            name: String::decode(decoder)?,
+            size: usize::decode(decoder)?,
+            kind: FileSystemEntryKind::decode(decoder)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct FileSystem {
+    root: PathBuf,
+    current_directory: PathBuf,
+    entries: HashMap<PathBuf, FileSystemEntry>,
+}
+
+impl FromStr for FileSystem {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut fs = FileSystem::new("/");
+
+        let all_commands = value.strip_prefix("$ ").ok_or_else(|| {
+            ParseError::at(
+                value,
+                value.lines().next().unwrap_or(&value[..0]),
+                ParseErrorKind::MissingCommandPrompt,
+            )
+        })?;
+
+        for command_str in all_commands.split("\n$ ") {
+            let command = command_str
+                .parse::<Command>()
+                .map_err(|error| error.within(value, command_str))?;
+
+            match command {
+                Command::ChangeDirectory(name) => {
+                    fs.change_current_directory(name).map_err(|kind| {
+                        ParseError::at(
+                            value,
+                            command_str.lines().next().unwrap_or(&command_str[..0]),
+                            kind,
+                        )
+                    })?
+                }
+                Command::List(listed_entries) => {
+                    for entry in listed_entries {
+                        match entry {
+                            ListedEntry::Directory(name) => fs.create_directory(name),
+                            ListedEntry::File(name, size) => fs.create_file(name, size),
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(fs)
+    }
+}
+
+impl Binary for FileSystem {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.root.encode(encoder);
+        self.current_directory.encode(encoder);
+        self.entries.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(Self {
+            root: PathBuf::decode(decoder)?,
+            current_directory: PathBuf::decode(decoder)?,
+            entries: HashMap::decode(decoder)?,
+        })
+    }
+}
+
+impl FileSystem {
+    pub fn new(root_directory_name: impl ToString) -> Self {
+        let mut entries = HashMap::new();
+
+        let root_path = PathBuf::from(root_directory_name.to_string());
+
+        let root_directory = FileSystemEntry::new_directory(root_path.to_string_lossy());
+
+        entries.insert(root_path.clone(), root_directory);
+
+        Self {
+            root: root_path.clone(),
+            entries,
+            current_directory: root_path,
+        }
+    }
+
+    pub fn create_directory(&mut self, name: impl ToString) {
+        let entry = FileSystemEntry::new_directory(name);
+
+        let new_entry_path = self.current_directory.join(entry.name.clone());
+
+        self.entries.insert(new_entry_path, entry);
+    }
+
+    pub fn create_file(&mut self, name: impl ToString, size: usize) {
+        let entry = FileSystemEntry::new_file(name, size);
+
+        let new_entry_path = self.current_directory.join(entry.name.clone());
+
+        for path in self.current_directory.ancestors() {
+            self.entries
+                .entry(path.to_path_buf())
+                .and_modify(|entry| entry.size += size)
+                .or_default();
+        }
+
+        self.entries.insert(new_entry_path, entry);
+    }
+
+    pub fn change_current_directory<N: AsRef<str> + Into<PathBuf>>(
+        &mut self,
+        directory_name: N,
+    ) -> Result<(), ParseErrorKind> {
+        match directory_name.as_ref() {
+            ".." => {
+                self.current_directory.pop();
+                Ok(())
+            }
+            name if name == self.root.to_str().unwrap() => {
+                self.current_directory = self.root.clone();
+                Ok(())
+            }
+            name => {
+                let path = self.current_directory.join(name);
+
+                match self.entries.get(&path) {
+                    Some(entry) => match entry.kind {
+                        FileSystemEntryKind::File => Err(ParseErrorKind::ChangeDirectoryIntoFile),
+                        FileSystemEntryKind::Directory => {
+                            self.current_directory = path;
+                            Ok(())
+                        }
+                    },
+                    None => Err(ParseErrorKind::ChangeDirectoryIntoMissingDirectory),
+                }
+            }
+        }
+    }
+
+    pub fn used_space(&self) -> usize {
+        self.entries
+            .get(&self.root)
+            .map(|root_entry| root_entry.size)
+            .unwrap_or(0)
+    }
+
+    pub fn free_space(&self, disk_size: usize) -> usize {
+        disk_size.saturating_sub(self.used_space())
+    }
+}
+
+impl FileSystem {
+    pub fn directories(&self) -> Vec<&FileSystemEntry> {
+        self.entries
+            .values()
+            .filter(|entry| entry.is_directory())
+            .collect()
+    }
+}
+
+pub const DISK_SIZE: usize = 70000000;
+pub const UPDATE_SIZE: usize = 30000000;
+pub const SIZE_LIMIT: usize = 100000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub disk_size: usize,
+    /// The free space the update needs.
+    pub update_size: usize,
+    /// The first part only adds up directories smaller than this.
+    pub size_limit: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            disk_size: DISK_SIZE,
+            update_size: UPDATE_SIZE,
+            size_limit: SIZE_LIMIT,
+        }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &["disk_size", "update_size", "size_limit"];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "disk_size" => self.disk_size = integer_at_least(key, value, 0)?,
+            "update_size" => self.update_size = integer_at_least(key, value, 0)?,
+            "size_limit" => self.size_limit = integer_at_least(key, value, 0)?,
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct NoSpaceLeftOnDevice;
+
+impl Solution for NoSpaceLeftOnDevice {
+    const DAY: u8 = 7;
+    const NAME: &'static str = "no_space_left_on_device";
+
+    type Input = FileSystem;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    type Generator = generator::ShellTranscript;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let terminal_output = input.trim();
+
+        Ok(terminal_output
+            .parse::<FileSystem>()
+            .map_err(|error| error.within(input, terminal_output).locate(input))?)
+    }
+
+    fn part_one_with(file_system: &Self::Input, config: &Self::Config) -> Result<Self::PartOne> {
+        Ok(file_system
+            .directories()
+            .iter()
+            .filter(|entry| entry.size < config.size_limit)
+            .map(|entry| entry.size)
+            .sum())
+    }
+
+    fn part_two_with(file_system: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        if file_system.used_space() > config.disk_size {
+            return Err(format!(
+                "The files take {} but the disk only has {}",
+                file_system.used_space(),
+                config.disk_size
+            )
+            .into());
+        }
+
+        let space_to_clear = config
+            .update_size
+            .saturating_sub(file_system.free_space(config.disk_size));
+
+        let smallest_directory_to_delete_for_update = file_system
+            .directories()
+            .into_iter()
+            .filter(|entry| entry.size > space_to_clear)
+            .min_by(|a, b| a.size.cmp(&b.size))
+            .ok_or("No directory is big enough to make room for the update")?;
+
+        Ok(smallest_directory_to_delete_for_update.size)
+    }
+}
+
+impl CachedSolution for NoSpaceLeftOnDevice {
+    const PARSER_VERSION: u32 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        cache::round_trip,
+        normalize::{normalize, variants},
+    };
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn entry_size(fs: &FileSystem, path: &str) -> usize {
+        fs.entries[&PathBuf::from(path)].size
+    }
+
+    #[test]
+    fn parses_listed_entries() {
+        assert!(matches!(
+            "dir a".parse::<ListedEntry>(),
+            Ok(ListedEntry::Directory(name)) if name == "a"
+        ));
+        assert!(matches!(
+            "14848514 b.txt".parse::<ListedEntry>(),
+            Ok(ListedEntry::File(name, 14848514)) if name == "b.txt"
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_listed_entries() {
+        let error = "12x b.txt".parse::<ListedEntry>().err().unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidFileSize);
+        assert_eq!(error.span, Span::new(0, 3));
+
+        let error = "b.txt".parse::<ListedEntry>().err().unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::MalformedListingEntry);
+    }
+
+    #[test]
+    fn parses_commands_with_their_output() {
+        assert!(matches!(
+            "cd a".parse::<Command>(),
+            Ok(Command::ChangeDirectory(name)) if name == "a"
+        ));
+        assert!(matches!(
+            "ls\ndir a\n10 b".parse::<Command>(),
+            Ok(Command::List(entries)) if entries.len() == 2
+        ));
+    }
+
+    #[test]
+    fn locates_listing_errors_inside_the_command() {
+        let error = "ls\ndir a\nb".parse::<Command>().err().unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::MalformedListingEntry);
+        assert_eq!(error.span, Span::new(9, 10));
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        let error = "rm -rf /".parse::<Command>().err().unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::UnknownCommand);
+        assert_eq!(error.span, Span::new(0, 8));
+
+        let error = "cd".parse::<Command>().err().unwrap();
+
+        assert_eq!(error.kind, ParseErrorKind::MissingDirectoryName);
+    }
+
+    #[test]
+    fn computes_directory_sizes_from_the_terminal_output() {
+        let fs = EXAMPLE.trim().parse::<FileSystem>().unwrap();
+
+        assert_eq!(entry_size(&fs, "/a/e"), 584);
+        assert_eq!(entry_size(&fs, "/a"), 94853);
+        assert_eq!(entry_size(&fs, "/d"), 24933642);
+        assert_eq!(entry_size(&fs, "/"), 48381165);
+        assert_eq!(fs.directories().len(), 4);
+        assert_eq!(fs.free_space(DISK_SIZE), 21618835);
+    }
+
+    #[test]
+    fn requires_the_output_to_start_with_a_command() {
+        let error = "dir a\n$ ls".parse::<FileSystem>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::MissingCommandPrompt);
+        assert_eq!(error.span, Span::new(0, 5));
+    }
+
+    #[test]
+    fn rejects_blank_output_without_a_command() {
+        for input in ["", "\n", " \n\t\n"] {
+            let error = input.parse::<FileSystem>().unwrap_err();
+
+            assert_eq!(error.kind, ParseErrorKind::MissingCommandPrompt);
+            assert!(NoSpaceLeftOnDevice::parse(input).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_changing_into_unknown_directories_or_files() {
+        let error = "$ cd /\n$ cd a".parse::<FileSystem>().unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::ChangeDirectoryIntoMissingDirectory
+        );
+        assert_eq!(error.span, Span::new(9, 13));
+
+        let error = "$ ls\n10 a\n$ cd a".parse::<FileSystem>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::ChangeDirectoryIntoFile);
+    }
+
+    #[test]
+    fn part_two_rejects_files_larger_than_the_disk() {
+        let file_system = NoSpaceLeftOnDevice::parse("$ cd /\n$ ls\n80000000 a").unwrap();
+
+        assert_eq!(file_system.free_space(DISK_SIZE), 0);
+        assert!(NoSpaceLeftOnDevice::part_two(&file_system).is_err());
+    }
+
+    #[test]
+    fn keeps_directory_sizes_through_a_cache_round_trip() {
+        let fs = round_trip(&NoSpaceLeftOnDevice::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(entry_size(&fs, "/a/e"), 584);
+        assert_eq!(entry_size(&fs, "/"), 48381165);
+        assert_eq!(fs.directories().len(), 4);
+        assert_eq!(fs.current_directory, PathBuf::from("/d"));
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = NoSpaceLeftOnDevice::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                NoSpaceLeftOnDevice::part_one(&input).unwrap(),
+                95437,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                NoSpaceLeftOnDevice::part_two(&input).unwrap(),
+                24933642,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = NoSpaceLeftOnDevice::parse(EXAMPLE).unwrap();
+
+        assert_eq!(NoSpaceLeftOnDevice::part_one(&input).unwrap(), 95437);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = NoSpaceLeftOnDevice::parse(EXAMPLE).unwrap();
+
+        assert_eq!(NoSpaceLeftOnDevice::part_two(&input).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn solves_with_configured_sizes() {
+        let input = NoSpaceLeftOnDevice::parse(EXAMPLE).unwrap();
+        let config = Config {
+            disk_size: 48500000,
+            update_size: 200000,
+            size_limit: 1000,
+        };
+
+        assert_eq!(
+            NoSpaceLeftOnDevice::part_one_with(&input, &config).unwrap(),
+            584
+        );
+        assert_eq!(
+            NoSpaceLeftOnDevice::part_two_with(&input, &config).unwrap(),
+            94853
+        );
+    }
+}
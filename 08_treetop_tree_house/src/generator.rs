@@ -0,0 +1,75 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+/// A grid of tree heights that tend to grow towards the middle, like the official forests.
+pub struct Forest {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Forest {
+    fn default() -> Self {
+        Self {
+            width: 99,
+            height: 99,
+        }
+    }
+}
+
+impl Generator for Forest {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+        let side_scale = (scale as f64).sqrt();
+
+        Self {
+            width: (default.width as f64 * side_scale).round() as usize,
+            height: (default.height as f64 * side_scale).round() as usize,
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let (width, height) = (self.width.max(1), self.height.max(1));
+
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let distance_to_edge = x.min(width - 1 - x).min(y).min(height - 1 - y);
+                        let tallest = (3 + distance_to_edge * 12 / width.min(height)).min(9);
+
+                        char::from_digit(rng.range(0..=tallest as u32), 10).unwrap()
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::TreetopTreeHouse;
+
+    #[test]
+    fn generates_grids_of_the_requested_size() {
+        for seed in 0..3 {
+            let forest = Forest {
+                width: 40,
+                height: 25,
+            };
+            let input = forest.generate(&mut Rng::new(seed));
+            let height_map = TreetopTreeHouse::parse(&input).unwrap();
+
+            assert_eq!((height_map.width(), height_map.height()), (40, 25));
+            assert!(TreetopTreeHouse::part_one(&height_map).unwrap() >= 2 * (40 + 25) - 4);
+            assert!(TreetopTreeHouse::part_two(&height_map).is_ok());
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<TreetopTreeHouse>(8, 50).unwrap();
+    }
+}
@@ -0,0 +1,379 @@
+pub mod generator;
+
+use std::fmt;
+
+use aoc_common::{
+    cache::CachedSolution,
+    diagnostic,
+    grid::{Grid, GridParseErrorKind, Offset, Position, ORTHOGONAL_OFFSETS},
+    variants::{SolutionVariants, Solver},
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    EmptyMap,
+    InvalidTreeHeight(char),
+    RaggedRow { expected_width: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMap => write!(f, "The map should have at least one tree"),
+            Self::InvalidTreeHeight(tree_height) => write!(
+                f,
+                "A tree height should be a digit from 0 to 9. Got: {:?}",
+                tree_height
+            ),
+            Self::RaggedRow { expected_width } => write!(
+                f,
+                "Every row of the map should have the same width as the first one ({} trees)",
+                expected_width
+            ),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub type TreeHeight = u8;
+pub type TreeHeightMap = Grid<TreeHeight>;
+
+pub type TreeVisibilityMap = Grid<bool>;
+
+pub type TreeScenicScore = u32;
+pub type TreeScenicScoreMap = Grid<TreeScenicScore>;
+
+pub fn parse_height_map(value: &str) -> Result<TreeHeightMap, ParseError> {
+    Grid::parse(value, |tree_height| {
+        tree_height
+            .to_digit(10)
+            .map(|tree_height| tree_height as TreeHeight)
+            .ok_or(ParseErrorKind::InvalidTreeHeight(tree_height))
+    })
+    .map_err(|error| {
+        let kind = match error.kind {
+            GridParseErrorKind::Empty => ParseErrorKind::EmptyMap,
+            GridParseErrorKind::RaggedRow { expected_width } => {
+                ParseErrorKind::RaggedRow { expected_width }
+            }
+            GridParseErrorKind::InvalidCell(kind) => kind,
+        };
+
+        ParseError::new(kind, error.span)
+    })
+}
+
+fn mark_visible_trees(
+    height_map: &TreeHeightMap,
+    visibility_map: &mut TreeVisibilityMap,
+    line_of_sight: impl Iterator<Item = Position>,
+) {
+    let mut tallest_tree_height = None;
+
+    for position in line_of_sight {
+        let tree_height = height_map[position];
+
+        if tallest_tree_height.is_none_or(|tallest| tree_height > tallest) {
+            visibility_map[position] = true;
+            tallest_tree_height = Some(tree_height);
+        }
+    }
+}
+
+pub fn visibility_map(height_map: &TreeHeightMap) -> TreeVisibilityMap {
+    let (width, height) = (height_map.width(), height_map.height());
+
+    let mut visibility_map = Grid::filled(width, height, false);
+
+    for y in 0..height {
+        let row = (0..width).map(|x| (x, y));
+
+        mark_visible_trees(height_map, &mut visibility_map, row.clone());
+        mark_visible_trees(height_map, &mut visibility_map, row.rev());
+    }
+
+    for x in 0..width {
+        let column = (0..height).map(|y| (x, y));
+
+        mark_visible_trees(height_map, &mut visibility_map, column.clone());
+        mark_visible_trees(height_map, &mut visibility_map, column.rev());
+    }
+
+    visibility_map
+}
+
+fn viewing_distance(height_map: &TreeHeightMap, position: Position, offset: Offset) -> u32 {
+    let tree_height = height_map[position];
+
+    let mut distance = 0;
+
+    for other_position in height_map.ray(position, offset) {
+        distance += 1;
+
+        if height_map[other_position] >= tree_height {
+            break;
+        }
+    }
+
+    distance
+}
+
+pub fn scenic_score_map(height_map: &TreeHeightMap) -> TreeScenicScoreMap {
+    height_map.map_indexed(|position, _| {
+        ORTHOGONAL_OFFSETS
+            .into_iter()
+            .map(|offset| viewing_distance(height_map, position, offset))
+            .product()
+    })
+}
+
+/// Multiplies the score of every tree along `line_of_sight` by how far it sees looking back
+/// towards where the line starts. The trees still in view from further along are kept on a stack,
+/// shortest on top, so each tree is pushed and popped once.
+fn multiply_viewing_distances(
+    height_map: &TreeHeightMap,
+    scenic_score_map: &mut TreeScenicScoreMap,
+    line_of_sight: impl Iterator<Item = Position>,
+) {
+    let mut blocking_trees: Vec<(usize, TreeHeight)> = vec![];
+
+    for (index, position) in line_of_sight.enumerate() {
+        let tree_height = height_map[position];
+
+        while blocking_trees
+            .last()
+            .is_some_and(|&(_, other_tree_height)| other_tree_height < tree_height)
+        {
+            blocking_trees.pop();
+        }
+
+        let distance = match blocking_trees.last() {
+            Some(&(blocking_index, _)) => index - blocking_index,
+            None => index,
+        };
+
+        scenic_score_map[position] *= distance as TreeScenicScore;
+        blocking_trees.push((index, tree_height));
+    }
+}
+
+/// Same as `scenic_score_map`, in a sweep per row and column direction instead of a walk per tree
+/// and direction.
+pub fn scenic_score_map_with_stacks(height_map: &TreeHeightMap) -> TreeScenicScoreMap {
+    let (width, height) = (height_map.width(), height_map.height());
+
+    let mut scenic_score_map = Grid::filled(width, height, 1);
+
+    for y in 0..height {
+        let row = (0..width).map(|x| (x, y));
+
+        multiply_viewing_distances(height_map, &mut scenic_score_map, row.clone());
+        multiply_viewing_distances(height_map, &mut scenic_score_map, row.rev());
+    }
+
+    for x in 0..width {
+        let column = (0..height).map(|y| (x, y));
+
+        multiply_viewing_distances(height_map, &mut scenic_score_map, column.clone());
+        multiply_viewing_distances(height_map, &mut scenic_score_map, column.rev());
+    }
+
+    scenic_score_map
+}
+
+pub struct TreetopTreeHouse;
+
+impl Solution for TreetopTreeHouse {
+    const DAY: u8 = 8;
+    const NAME: &'static str = "treetop_tree_house";
+
+    type Input = TreeHeightMap;
+    type PartOne = usize;
+    type PartTwo = TreeScenicScore;
+
+    type Generator = generator::Forest;
+
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_height_map(input).map_err(|error| error.locate(input))?)
+    }
+
+    fn part_one_with(height_map: &Self::Input, _: &Self::Config) -> Result<Self::PartOne> {
+        Ok(visibility_map(height_map)
+            .cells()
+            .filter(|&&is_visible| is_visible)
+            .count())
+    }
+
+    fn part_two_with(height_map: &Self::Input, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(scenic_score_map(height_map)
+            .cells()
+            .copied()
+            .max()
+            .unwrap_or_default())
+    }
+}
+
+impl SolutionVariants for TreetopTreeHouse {
+    fn part_two_solvers() -> Vec<Solver<Self, Self::PartTwo>> {
+        vec![
+            Solver {
+                name: "walk from every tree",
+                solve: Self::part_two_with,
+            },
+            Solver {
+                name: "monotonic stack",
+                solve: |height_map, _| {
+                    Ok(scenic_score_map_with_stacks(height_map)
+                        .cells()
+                        .copied()
+                        .max()
+                        .unwrap_or_default())
+                },
+            },
+        ]
+    }
+}
+
+impl CachedSolution for TreetopTreeHouse {
+    const PARSER_VERSION: u32 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        cache::round_trip,
+        diagnostic::Span,
+        generator::Generator,
+        normalize::{normalize, variants},
+        random::Rng,
+        variants::cross_check,
+    };
+
+    use crate::generator::Forest;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn height_map() -> TreeHeightMap {
+        parse_height_map(EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_tree_heights_row_by_row() {
+        let height_map = height_map();
+
+        assert_eq!((height_map.height(), height_map.width()), (5, 5));
+        assert_eq!(height_map.row(0), &[3, 0, 3, 7, 3]);
+        assert_eq!(
+            height_map.column(0).copied().collect::<Vec<_>>(),
+            vec![3, 2, 6, 3, 3]
+        );
+        assert_eq!(height_map[(4, 4)], 0);
+    }
+
+    #[test]
+    fn rejects_invalid_tree_heights() {
+        let error = parse_height_map("123\n4x6").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidTreeHeight('x'));
+        assert_eq!(error.span, Span::new(5, 6));
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let error = parse_height_map("123\n45\n789").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::RaggedRow { expected_width: 3 });
+        assert_eq!(error.span, Span::new(4, 6));
+    }
+
+    #[test]
+    fn rejects_empty_maps() {
+        let error = parse_height_map("\n").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::EmptyMap);
+    }
+
+    #[test]
+    fn marks_only_hidden_interior_trees_as_invisible() {
+        let visibility_map = visibility_map(&height_map());
+
+        assert_eq!(visibility_map.row(1), &[true, true, true, false, true]);
+        assert_eq!(visibility_map.row(2), &[true, true, false, true, true]);
+        assert_eq!(visibility_map.row(3), &[true, false, true, false, true]);
+    }
+
+    #[test]
+    fn scores_the_view_from_each_tree() {
+        let scenic_score_map = scenic_score_map(&height_map());
+
+        assert_eq!(scenic_score_map[(2, 1)], 4);
+        assert_eq!(scenic_score_map[(2, 3)], 8);
+        assert_eq!(scenic_score_map[(0, 2)], 0);
+        assert_eq!(scenic_score_map[(4, 2)], 0);
+    }
+
+    #[test]
+    fn scores_the_same_views_with_monotonic_stacks() {
+        let height_maps = ["5", "3132", "3\n1\n3\n2"]
+            .map(|trees| parse_height_map(trees).unwrap())
+            .into_iter()
+            .chain([height_map()])
+            .chain((1..=3).map(|seed| {
+                TreetopTreeHouse::parse(&Forest::default().generate(&mut Rng::new(seed))).unwrap()
+            }));
+
+        for height_map in height_maps {
+            assert_eq!(
+                scenic_score_map_with_stacks(&height_map),
+                scenic_score_map(&height_map)
+            );
+
+            cross_check::<TreetopTreeHouse>(&height_map, &()).unwrap();
+        }
+    }
+
+    #[test]
+    fn keeps_tree_heights_through_a_cache_round_trip() {
+        assert_eq!(round_trip(&height_map()).unwrap(), height_map());
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = TreetopTreeHouse::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                TreetopTreeHouse::part_one(&input).unwrap(),
+                21,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                TreetopTreeHouse::part_two(&input).unwrap(),
+                8,
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = TreetopTreeHouse::parse(EXAMPLE).unwrap();
+
+        assert_eq!(TreetopTreeHouse::part_one(&input).unwrap(), 21);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = TreetopTreeHouse::parse(EXAMPLE).unwrap();
+
+        assert_eq!(TreetopTreeHouse::part_two(&input).unwrap(), 8);
+    }
+}
@@ -0,0 +1,66 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{generator::Generator, random::Rng};
+
+pub struct HeadMotions {
+    pub motions: usize,
+    pub steps_per_motion: RangeInclusive<u32>,
+}
+
+impl Default for HeadMotions {
+    fn default() -> Self {
+        Self {
+            motions: 2000,
+            steps_per_motion: 1..=19,
+        }
+    }
+}
+
+impl Generator for HeadMotions {
+    fn scaled(scale: usize) -> Self {
+        let default = Self::default();
+
+        Self {
+            motions: default.motions * scale,
+            ..default
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.motions)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&['U', 'D', 'L', 'R']),
+                    rng.range(self.steps_per_motion.clone())
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::RopeBridge;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        for seed in 0..10 {
+            let input = HeadMotions::default().generate(&mut Rng::new(seed));
+            let motions = RopeBridge::parse(&input).unwrap();
+
+            assert_eq!(motions.len(), 2000);
+            assert!(
+                RopeBridge::part_two(&motions).unwrap() <= RopeBridge::part_one(&motions).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<RopeBridge>(9, 100).unwrap();
+    }
+}
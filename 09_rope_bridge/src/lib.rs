@@ -0,0 +1,279 @@
+pub mod generator;
+
+use std::{collections::HashSet, fmt, str::FromStr};
+
+use aoc_common::{
+    config::{self, integer_at_least, unknown_key},
+    diagnostic::{self, parse_lines, Span},
+    geometry::{Direction4, Point2},
+    toml::Value,
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidDirection,
+    MissingSeparator,
+    InvalidAmount,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDirection => {
+                write!(f, "A direction should be U, D, L or R (or N, S, W or E)")
+            }
+            Self::MissingSeparator => write!(
+                f,
+                "A motion's direction and amount should be separated by a space"
+            ),
+            Self::InvalidAmount => write!(f, "A motion amount should be an integer"),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+pub type Point = Point2<i32>;
+
+pub struct Motion {
+    direction: Direction4,
+    amount: i32,
+}
+
+impl FromStr for Motion {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (direction_str, amount_str) = value.split_once(' ').ok_or_else(|| {
+            ParseError::new(ParseErrorKind::MissingSeparator, Span::end_of(value))
+        })?;
+
+        let amount = amount_str
+            .parse::<i32>()
+            .map_err(|_| ParseError::at(value, amount_str, ParseErrorKind::InvalidAmount))?;
+
+        let direction = direction_str
+            .parse()
+            .map_err(|_| ParseError::at(value, direction_str, ParseErrorKind::InvalidDirection))?;
+
+        Ok(Self { direction, amount })
+    }
+}
+
+pub struct Rope {
+    knots: Vec<Point>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize, initial_position: &Point) -> Self {
+        Self {
+            knots: [*initial_position].repeat(knot_count),
+        }
+    }
+
+    pub fn tail(&self) -> &Point {
+        self.knots.last().unwrap()
+    }
+
+    pub fn move_rope_head(&mut self, direction: Direction4) {
+        let head_knot_position = self.knots[0] + direction.offset();
+
+        let mut new_knot_positions = vec![head_knot_position];
+        let mut last_knot_moved = head_knot_position;
+
+        for knot in self.knots[1..].iter() {
+            last_knot_moved = Rope::get_new_knot_position(knot, &last_knot_moved);
+
+            new_knot_positions.push(last_knot_moved);
+        }
+
+        self.knots = new_knot_positions;
+    }
+
+    fn get_new_knot_position(current_position: &Point, previous_knot_position: &Point) -> Point {
+        if current_position.chebyshev_distance(*previous_knot_position) <= 1 {
+            return *current_position;
+        }
+
+        *current_position + (*previous_knot_position - *current_position).signum()
+    }
+}
+
+pub fn compute_unique_tail_positions_count(rope: &mut Rope, motions: &[Motion]) -> usize {
+    let mut unique_tail_positions = HashSet::<Point>::new();
+
+    for motion in motions {
+        for _ in 0..motion.amount {
+            rope.move_rope_head(motion.direction);
+
+            unique_tail_positions.insert(*rope.tail());
+        }
+    }
+
+    unique_tail_positions.len()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// How many knots the rope of the first part has, counting the head.
+    pub short_rope_knots: usize,
+    /// How many knots the rope of the second part has, counting the head.
+    pub long_rope_knots: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            short_rope_knots: 2,
+            long_rope_knots: 10,
+        }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &["short_rope_knots", "long_rope_knots"];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "short_rope_knots" => self.short_rope_knots = integer_at_least(key, value, 1)?,
+            "long_rope_knots" => self.long_rope_knots = integer_at_least(key, value, 1)?,
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RopeBridge;
+
+impl Solution for RopeBridge {
+    const DAY: u8 = 9;
+    const NAME: &'static str = "rope_bridge";
+
+    type Input = Vec<Motion>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    type Generator = generator::HeadMotions;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input, str::parse::<Motion>)?)
+    }
+
+    fn part_one_with(motions: &Self::Input, config: &Self::Config) -> Result<Self::PartOne> {
+        let mut short_rope = Rope::new(config.short_rope_knots, &Point::default());
+
+        Ok(compute_unique_tail_positions_count(
+            &mut short_rope,
+            motions,
+        ))
+    }
+
+    fn part_two_with(motions: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        let mut long_rope = Rope::new(config.long_rope_knots, &Point::default());
+
+        Ok(compute_unique_tail_positions_count(&mut long_rope, motions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::normalize::{normalize, variants};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+    const LARGER_EXAMPLE: &str = include_str!("../fixtures/larger_example.txt");
+
+    fn motion(value: &str) -> Motion {
+        value.parse::<Motion>().ok().unwrap()
+    }
+
+    fn parse_error(value: &str) -> ParseError {
+        value.parse::<Motion>().err().unwrap()
+    }
+
+    #[test]
+    fn parses_motions() {
+        let motion = motion("L 12");
+
+        assert_eq!(motion.direction, Direction4::Left);
+        assert_eq!(motion.amount, 12);
+    }
+
+    #[test]
+    fn rejects_malformed_motions() {
+        let error = parse_error("X 1");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidDirection);
+        assert_eq!(error.span, Span::new(0, 1));
+
+        let error = parse_error("U one");
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidAmount);
+        assert_eq!(error.span, Span::new(2, 5));
+
+        assert_eq!(parse_error("U1").kind, ParseErrorKind::MissingSeparator);
+    }
+
+    #[test]
+    fn accepts_compass_directions() {
+        assert_eq!(motion("N 3").direction, Direction4::Up);
+        assert_eq!(motion("W 3").direction, Direction4::Left);
+    }
+
+    #[test]
+    fn tail_follows_the_head_diagonally() {
+        let mut rope = Rope::new(2, &Point::default());
+
+        rope.move_rope_head(Direction4::Right);
+        assert_eq!(rope.tail(), &Point::default());
+
+        rope.move_rope_head(Direction4::Up);
+        assert_eq!(rope.tail(), &Point::default());
+
+        rope.move_rope_head(Direction4::Up);
+        assert_eq!(rope.tail(), &Point::new(1, -1));
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = RopeBridge::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(RopeBridge::part_one(&input).unwrap(), 13, "{}", variant);
+            assert_eq!(RopeBridge::part_two(&input).unwrap(), 1, "{}", variant);
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = RopeBridge::parse(EXAMPLE).unwrap();
+
+        assert_eq!(RopeBridge::part_one(&input).unwrap(), 13);
+    }
+
+    #[test]
+    fn solves_part_two_examples() {
+        let input = RopeBridge::parse(EXAMPLE).unwrap();
+        let larger_input = RopeBridge::parse(LARGER_EXAMPLE).unwrap();
+
+        assert_eq!(RopeBridge::part_two(&input).unwrap(), 1);
+        assert_eq!(RopeBridge::part_two(&larger_input).unwrap(), 36);
+    }
+
+    #[test]
+    fn solves_with_configured_knot_counts() {
+        let input = RopeBridge::parse(EXAMPLE).unwrap();
+        let config = Config {
+            short_rope_knots: 1,
+            long_rope_knots: 2,
+        };
+
+        assert_eq!(RopeBridge::part_one_with(&input, &config).unwrap(), 20);
+        assert_eq!(RopeBridge::part_two_with(&input, &config).unwrap(), 13);
+    }
+}
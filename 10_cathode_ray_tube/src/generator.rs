@@ -0,0 +1,67 @@
+use aoc_common::{generator::Generator, random::Rng};
+
+/// A program whose register stays within the screen, so it draws something on every row.
+pub struct Program {
+    pub instructions: usize,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Self { instructions: 140 }
+    }
+}
+
+impl Generator for Program {
+    fn scaled(scale: usize) -> Self {
+        Self {
+            instructions: Self::default().instructions * scale,
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let mut x = 1;
+
+        (0..self.instructions)
+            .map(|_| match rng.chance(0.3) {
+                true => "noop\n".to_string(),
+                false => {
+                    let target = rng.range(0..=39);
+                    let value = (target - x).clamp(-20, 20);
+
+                    x += value;
+
+                    format!("addx {}\n", value)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{generator::fuzz, Solution};
+
+    use super::*;
+    use crate::{run_program, CathodeRayTube, Config};
+
+    #[test]
+    fn generates_programs_running_past_the_last_pixel() {
+        for seed in 0..10 {
+            let input = Program::default().generate(&mut Rng::new(seed));
+            let instructions = CathodeRayTube::parse(&input).unwrap();
+
+            assert!(
+                run_program(&instructions, &Config::default())
+                    .signal_strengths
+                    .len()
+                    >= 5
+            );
+            assert!(CathodeRayTube::part_two(&instructions).is_ok());
+        }
+    }
+
+    #[test]
+    fn survives_mutated_inputs() {
+        fuzz::<CathodeRayTube>(10, 200).unwrap();
+    }
+}
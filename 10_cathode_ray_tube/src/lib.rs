@@ -0,0 +1,544 @@
+pub mod generator;
+
+use std::{fmt, io::BufRead, str::FromStr};
+
+use aoc_common::{
+    binary::{Binary, Decoder, Encoder},
+    cache::CachedSolution,
+    config::{self, integer_at_least, unknown_key},
+    diagnostic::{self, parse_lines, Span},
+    geometry::Point2,
+    grid::Grid,
+    stream::{parse_stream_lines, StreamingSolution},
+    toml::Value,
+    Result, Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidInstruction,
+    MissingAddxValue,
+    InvalidAddxValue,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInstruction => write!(f, "An instruction should be noop or addx"),
+            Self::MissingAddxValue => write!(f, "An ADDX instruction should have a value"),
+            Self::InvalidAddxValue => {
+                write!(f, "An ADDX instruction value should be an integer")
+            }
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+#[derive(Debug)]
+pub enum Instruction {
+    Addx(i32),
+    Noop,
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(instruction_str: &str) -> Result<Self, Self::Err> {
+        match instruction_str.split_once(' ') {
+            None if instruction_str == "noop" => Ok(Self::Noop),
+            None if instruction_str == "addx" => Err(ParseError::new(
+                ParseErrorKind::MissingAddxValue,
+                Span::end_of(instruction_str),
+            )),
+            Some(("addx", value_str)) => {
+                let value = value_str.parse::<i32>().map_err(|_| {
+                    ParseError::at(instruction_str, value_str, ParseErrorKind::InvalidAddxValue)
+                })?;
+
+                Ok(Instruction::Addx(value))
+            }
+            _ => Err(ParseError::at(
+                instruction_str,
+                instruction_str,
+                ParseErrorKind::InvalidInstruction,
+            )),
+        }
+    }
+}
+
+impl Binary for Instruction {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Addx(value) => {
+                encoder.write_u8(0);
+                value.encode(encoder);
+            }
+            Self::Noop => encoder.write_u8(1),
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        match decoder.read_u8()? {
+            0 => Ok(Self::Addx(i32::decode(decoder)?)),
+            1 => Ok(Self::Noop),
+            tag => Err(format!("{} is not an encoded instruction", tag).into()),
+        }
+    }
+}
+
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+/// Screens past this many pixels would take more memory to draw than anyone should want to read.
+const MAX_SCREEN_PIXELS: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub screen_width: usize,
+    pub screen_height: usize,
+    /// The first cycle whose signal strength the first part adds up.
+    pub first_signal_cycle: u32,
+    /// How many cycles there are between two signal strengths the first part adds up.
+    pub signal_cycle_interval: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            screen_width: SCREEN_WIDTH,
+            screen_height: SCREEN_HEIGHT,
+            first_signal_cycle: 20,
+            signal_cycle_interval: 40,
+        }
+    }
+}
+
+impl config::Config for Config {
+    const KEYS: &'static [&'static str] = &[
+        "screen_width",
+        "screen_height",
+        "first_signal_cycle",
+        "signal_cycle_interval",
+    ];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        match key {
+            "screen_width" => self.screen_width = integer_at_least(key, value, 1)?,
+            "screen_height" => self.screen_height = integer_at_least(key, value, 1)?,
+            "first_signal_cycle" => self.first_signal_cycle = integer_at_least(key, value, 1)?,
+            "signal_cycle_interval" => {
+                self.signal_cycle_interval = integer_at_least(key, value, 1)?
+            }
+            _ => return Err(unknown_key(key, Self::KEYS)),
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self.screen_width.checked_mul(self.screen_height) {
+            Some(pixels) if pixels <= MAX_SCREEN_PIXELS => Ok(()),
+            _ => Err(format!(
+                "screen_width = {} and screen_height = {} make a screen of more than {} pixels",
+                self.screen_width, self.screen_height, MAX_SCREEN_PIXELS
+            )
+            .into()),
+        }
+    }
+}
+
+pub struct Crt {
+    pixels: Grid<bool>,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new(SCREEN_WIDTH, SCREEN_HEIGHT)
+    }
+}
+
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let screen = self.pixels.render(|&is_lit| if is_lit { '#' } else { '.' });
+
+        write!(f, "{}", screen)
+    }
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            pixels: Grid::filled(width, height, false),
+        }
+    }
+
+    pub fn draw_pixel(&mut self, cycle: u32, sprite_position: i32) {
+        let width = self.pixels.width();
+        let current_pixel = Point2::new(cycle as usize % width, cycle as usize / width);
+
+        let sprite_center = Point2::new(i64::from(sprite_position), 0);
+        let beam = Point2::new(current_pixel.x as i64, 0);
+
+        if beam.chebyshev_distance(sprite_center) > 1 {
+            return;
+        }
+
+        if let Some(pixel) = self.pixels.get_mut(current_pixel.into()) {
+            *pixel = true;
+        }
+    }
+}
+
+pub struct Cpu {
+    x: i32,
+    crt: Crt,
+    current_cycle: u32,
+    first_signal_cycle: u32,
+    signal_cycle_interval: u32,
+    signal_strengths: Vec<i64>,
+}
+
+impl Cpu {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            x: 1,
+            current_cycle: 0,
+            crt: Crt::new(config.screen_width, config.screen_height),
+            first_signal_cycle: config.first_signal_cycle,
+            signal_cycle_interval: config.signal_cycle_interval,
+            signal_strengths: vec![],
+        }
+    }
+
+    fn tick(&mut self) {
+        self.crt.draw_pixel(self.current_cycle, self.x);
+
+        self.current_cycle += 1;
+
+        let is_signal_cycle = self
+            .current_cycle
+            .checked_sub(self.first_signal_cycle)
+            .is_some_and(|since_first| since_first % self.signal_cycle_interval == 0);
+
+        if is_signal_cycle {
+            self.signal_strengths
+                .push(self.x as i64 * self.current_cycle as i64);
+        }
+    }
+
+    pub fn run(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Noop => self.tick(),
+            Instruction::Addx(value) => {
+                self.tick();
+                self.tick();
+                self.x += value;
+            }
+        }
+    }
+}
+
+pub fn run_program(instructions: &[Instruction], config: &Config) -> Cpu {
+    let mut cpu = Cpu::new(config);
+
+    for instruction in instructions {
+        cpu.run(instruction)
+    }
+
+    cpu
+}
+
+pub struct CathodeRayTube;
+
+impl Solution for CathodeRayTube {
+    const DAY: u8 = 10;
+    const NAME: &'static str = "cathode_ray_tube";
+
+    type Input = Vec<Instruction>;
+    type PartOne = i64;
+    type PartTwo = String;
+
+    type Generator = generator::Program;
+
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input, str::parse::<Instruction>)?)
+    }
+
+    fn part_one_with(instructions: &Self::Input, config: &Self::Config) -> Result<Self::PartOne> {
+        Ok(run_program(instructions, config)
+            .signal_strengths
+            .iter()
+            .sum::<i64>())
+    }
+
+    fn part_two_with(instructions: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(run_program(instructions, config).crt.to_string())
+    }
+}
+
+impl CachedSolution for CathodeRayTube {
+    const PARSER_VERSION: u32 = 1;
+}
+
+impl StreamingSolution for CathodeRayTube {
+    /// The sum of the signal strengths and the screen.
+    type Summary = (i64, Crt);
+
+    fn summarize_stream(reader: &mut dyn BufRead, config: &Self::Config) -> Result<Self::Summary> {
+        let mut cpu = Cpu::new(config);
+        let mut signal_strength_sum = 0;
+
+        parse_stream_lines(reader, str::parse::<Instruction>, |instruction| {
+            cpu.run(&instruction);
+
+            signal_strength_sum += cpu.signal_strengths.drain(..).sum::<i64>();
+        })?;
+
+        Ok((signal_strength_sum, cpu.crt))
+    }
+
+    fn part_one_from_summary(
+        (signal_strength_sum, _): &Self::Summary,
+        _: &Self::Config,
+    ) -> Result<Self::PartOne> {
+        Ok(*signal_strength_sum)
+    }
+
+    fn part_two_from_summary((_, crt): &Self::Summary, _: &Self::Config) -> Result<Self::PartTwo> {
+        Ok(crt.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        cache::round_trip,
+        config::Config as _,
+        generator::Generator,
+        normalize::{normalize, variants, NormalizedReader},
+        random::Rng,
+        toml::Table,
+    };
+
+    use crate::generator::Program;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+    const EXAMPLE_IMAGE: &str = include_str!("../fixtures/example_image.txt");
+
+    #[test]
+    fn parses_instructions() {
+        assert!(matches!("noop".parse(), Ok(Instruction::Noop)));
+        assert!(matches!("addx -5".parse(), Ok(Instruction::Addx(-5))));
+    }
+
+    #[test]
+    fn rejects_malformed_instructions() {
+        let error = "addx five".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidAddxValue);
+        assert_eq!(error.span, Span::new(5, 9));
+
+        let error = "addx".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::MissingAddxValue);
+        assert_eq!(error.span, Span::new(4, 4));
+
+        let error = "noop 1".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidInstruction);
+    }
+
+    #[test]
+    fn addx_takes_two_cycles() {
+        let instructions = CathodeRayTube::parse("noop\naddx 3\naddx -5").unwrap();
+
+        let cpu = run_program(&instructions, &Config::default());
+
+        assert_eq!(cpu.current_cycle, 5);
+        assert_eq!(cpu.x, -1);
+    }
+
+    #[test]
+    fn runs_programs_longer_than_a_u16_of_cycles() {
+        let instructions = CathodeRayTube::parse(&"noop\n".repeat(70000)).unwrap();
+
+        let cpu = run_program(&instructions, &Config::default());
+
+        assert_eq!(cpu.current_cycle, 70000);
+        assert_eq!(cpu.signal_strengths.len(), 1750);
+    }
+
+    #[test]
+    fn saves_signal_strengths_every_forty_cycles() {
+        let instructions = CathodeRayTube::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            run_program(&instructions, &Config::default()).signal_strengths,
+            vec![420, 1140, 1800, 2940, 2880, 3960]
+        );
+    }
+
+    #[test]
+    fn lights_pixels_under_the_three_pixel_sprite() {
+        let mut crt = Crt::default();
+
+        for cycle in 0..5 {
+            crt.draw_pixel(cycle, 2);
+        }
+
+        crt.draw_pixel(40, -1);
+        crt.draw_pixel(79, 40);
+
+        let screen = crt.to_string();
+        let mut rows = screen.lines();
+
+        assert!(rows.next().unwrap().starts_with(".###."));
+        assert_eq!(rows.next().unwrap(), format!("#{}#", ".".repeat(38)));
+    }
+
+    #[test]
+    fn ignores_pixels_drawn_past_the_last_row() {
+        let mut crt = Crt::default();
+
+        crt.draw_pixel(240, 0);
+        crt.draw_pixel(241, 0);
+
+        assert!(!crt.to_string().contains('#'));
+    }
+
+    #[test]
+    fn runs_with_configured_screen_and_signal_cycles() {
+        let instructions = CathodeRayTube::parse(EXAMPLE).unwrap();
+        let config = Config {
+            screen_width: 20,
+            screen_height: 2,
+            first_signal_cycle: 60,
+            signal_cycle_interval: 80,
+        };
+
+        let cpu = run_program(&instructions, &config);
+
+        assert_eq!(cpu.signal_strengths, vec![1140, 2940, 3960]);
+        assert_eq!(cpu.crt.to_string().lines().count(), 2);
+        assert!(cpu.crt.to_string().lines().all(|row| row.len() == 20));
+    }
+
+    #[test]
+    fn rejects_screens_too_large_to_draw() {
+        let table = |screen_width, screen_height| {
+            Table::from([
+                ("screen_width".to_string(), Value::Integer(screen_width)),
+                ("screen_height".to_string(), Value::Integer(screen_height)),
+            ])
+        };
+
+        assert!(Config::from_table(&table(1024, 1024)).is_ok());
+        assert!(Config::from_table(&table(1 << 30, 1 << 30))
+            .unwrap_err()
+            .to_string()
+            .starts_with("screen_width = 1073741824 and screen_height = 1073741824"));
+        assert!(Config::from_table(&table(i64::MAX.into(), i64::MAX.into())).is_err());
+    }
+
+    #[test]
+    fn streams_the_same_answers_as_the_batch_solver() {
+        for input in [
+            EXAMPLE.to_string(),
+            Program::default().generate(&mut Rng::new(1)),
+        ] {
+            let parsed_input = CathodeRayTube::parse(&input).unwrap();
+            let summary =
+                CathodeRayTube::summarize_stream(&mut input.as_bytes(), &Config::default())
+                    .unwrap();
+
+            assert_eq!(
+                CathodeRayTube::part_one_from_summary(&summary, &Config::default()).unwrap(),
+                CathodeRayTube::part_one(&parsed_input).unwrap()
+            );
+            assert_eq!(
+                CathodeRayTube::part_two_from_summary(&summary, &Config::default()).unwrap(),
+                CathodeRayTube::part_two(&parsed_input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn solves_the_same_after_a_cache_round_trip() {
+        let parsed_input = CathodeRayTube::parse(EXAMPLE).unwrap();
+        let cached_input = round_trip(&parsed_input).unwrap();
+
+        assert!(matches!(
+            cached_input[..2],
+            [Instruction::Addx(15), Instruction::Addx(-11)]
+        ));
+        assert_eq!(
+            CathodeRayTube::part_one(&cached_input).unwrap(),
+            CathodeRayTube::part_one(&parsed_input).unwrap()
+        );
+        assert_eq!(
+            CathodeRayTube::part_two(&cached_input).unwrap(),
+            CathodeRayTube::part_two(&parsed_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn solves_crlf_and_bom_variants_of_the_example() {
+        for (variant, text) in variants(EXAMPLE) {
+            let input = CathodeRayTube::parse(&normalize(&text)).unwrap();
+
+            assert_eq!(
+                CathodeRayTube::part_one(&input).unwrap(),
+                13140,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                CathodeRayTube::part_two(&input).unwrap(),
+                EXAMPLE_IMAGE.trim_end(),
+                "{}",
+                variant
+            );
+
+            let summary = CathodeRayTube::summarize_stream(
+                &mut NormalizedReader::new(text.as_bytes()),
+                &Config::default(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                CathodeRayTube::part_one_from_summary(&summary, &Config::default()).unwrap(),
+                13140,
+                "{}",
+                variant
+            );
+            assert_eq!(
+                CathodeRayTube::part_two_from_summary(&summary, &Config::default()).unwrap(),
+                EXAMPLE_IMAGE.trim_end(),
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn solves_part_one_example() {
+        let input = CathodeRayTube::parse(EXAMPLE).unwrap();
+
+        assert_eq!(CathodeRayTube::part_one(&input).unwrap(), 13140);
+    }
+
+    #[test]
+    fn solves_part_two_example() {
+        let input = CathodeRayTube::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            CathodeRayTube::part_two(&input).unwrap(),
+            EXAMPLE_IMAGE.trim_end()
+        );
+    }
+}
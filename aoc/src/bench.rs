@@ -0,0 +1,388 @@
+use std::{
+    fs,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    answer::Part,
+    cache::InputCache,
+    input::{workspace_root, InputSource},
+    toml::{Document, Table, Value},
+    Result,
+};
+
+use crate::{
+    cli::{BenchInput, BenchOptions},
+    days::{day_table, find_cached_day, find_day, CachedDay, Day, DAYS},
+    output::format_duration,
+    PARTS,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Parse,
+    /// Reading the parsed input from the cache, instead of parsing it.
+    Load,
+    Solve(Part),
+}
+
+impl Stage {
+    fn key(&self) -> String {
+        match self {
+            Self::Parse => "parse".to_string(),
+            Self::Load => "load".to_string(),
+            Self::Solve(part) => format!("part{}", part),
+        }
+    }
+}
+
+struct Measurement {
+    stage: Stage,
+    median: Duration,
+    p95: Duration,
+}
+
+/// The nearest-rank percentile of sorted `samples`, which is an exact sample value rather than an
+/// interpolation.
+pub fn percentile(samples: &[Duration], rank: usize) -> Duration {
+    samples[(rank * samples.len()).div_ceil(100).max(1) - 1]
+}
+
+impl Measurement {
+    fn from_samples(stage: Stage, mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        Self {
+            stage,
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+        }
+    }
+}
+
+pub fn time<T>(run: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let output = black_box(run());
+
+    (output, start.elapsed())
+}
+
+fn bench_day(
+    day: &dyn Day,
+    cached: Option<(&dyn CachedDay, &InputCache)>,
+    input: &str,
+    options: &BenchOptions,
+) -> Result<Vec<Measurement>> {
+    let mut samples = vec![vec![]; 1 + PARTS.len()];
+
+    // Timings are compared against a baseline, so they always use the puzzle's own constants.
+    let config = day.configure(&Table::new())?;
+
+    // Fills the cache first, so that every sample reads from it even without warmup.
+    if let Some((cached_day, cache)) = cached {
+        cached_day.parse(cache, input)?;
+    }
+
+    for iteration in 0..options.warmup + options.iterations {
+        let (parsed_input, parse_time) = time(|| match cached {
+            Some((cached_day, cache)) => cached_day
+                .parse(cache, input)
+                .map(|(parsed_input, _)| parsed_input),
+            None => day.parse(input),
+        });
+        let parsed_input = parsed_input?;
+
+        let mut iteration_samples = vec![parse_time];
+
+        for part in PARTS {
+            let (answer, solve_time) =
+                time(|| day.solve(parsed_input.as_ref(), config.as_ref(), part));
+            answer?;
+
+            iteration_samples.push(solve_time);
+        }
+
+        if iteration >= options.warmup {
+            for (stage_samples, sample) in samples.iter_mut().zip(iteration_samples) {
+                stage_samples.push(sample);
+            }
+        }
+    }
+
+    let first_stage = match cached {
+        Some(_) => Stage::Load,
+        None => Stage::Parse,
+    };
+    let stages = [first_stage].into_iter().chain(PARTS.map(Stage::Solve));
+
+    Ok(stages
+        .zip(samples)
+        .map(|(stage, samples)| Measurement::from_samples(stage, samples))
+        .collect())
+}
+
+pub fn day_input(day: &dyn Day, input: &BenchInput) -> Result<(String, String)> {
+    match input {
+        BenchInput::Resolved(argument) => {
+            let source = day.input_source(argument.as_deref());
+
+            // Baselines may be shared between checkouts, so inputs inside the workspace are
+            // named relative to it.
+            let input_name = match &source {
+                InputSource::File(path) => path
+                    .strip_prefix(workspace_root())
+                    .unwrap_or(path)
+                    .display()
+                    .to_string(),
+                InputSource::Stdin => source.to_string(),
+            };
+
+            Ok((source.read()?, input_name))
+        }
+        BenchInput::Generated { seed, scale } => Ok((
+            day.generate(*seed, *scale),
+            format!("generated (seed {}, scale {})", seed, scale),
+        )),
+    }
+}
+
+fn median_key(stage: Stage) -> String {
+    format!("{}_median_ns", stage.key())
+}
+
+fn p95_key(stage: Stage) -> String {
+    format!("{}_p95_ns", stage.key())
+}
+
+enum Comparison {
+    Missing,
+    OtherInput,
+    Change { baseline: Duration, change: f64 },
+}
+
+fn compare(
+    baseline: &Document,
+    day: u8,
+    input_name: &str,
+    measurement: &Measurement,
+) -> Comparison {
+    let table = day_table(day);
+
+    match baseline.get(&table, "input") {
+        Some(Value::String(baseline_input)) if baseline_input != input_name => {
+            return Comparison::OtherInput
+        }
+        _ => {}
+    }
+
+    match baseline.get(&table, &median_key(measurement.stage)) {
+        Some(&Value::Integer(baseline_nanos)) if baseline_nanos > 0 => Comparison::Change {
+            baseline: Duration::from_nanos(baseline_nanos as u64),
+            change: measurement.median.as_nanos() as f64 / baseline_nanos as f64 - 1.0,
+        },
+        _ => Comparison::Missing,
+    }
+}
+
+fn print_measurement(
+    day: u8,
+    measurement: &Measurement,
+    comparison: &Comparison,
+    threshold: f64,
+) -> bool {
+    let (baseline, change, verdict, regressed) = match *comparison {
+        Comparison::Missing => ("-".to_string(), "-".to_string(), "", false),
+        Comparison::OtherInput => (
+            "-".to_string(),
+            "-".to_string(),
+            "baseline used another input",
+            false,
+        ),
+        Comparison::Change { baseline, change } => (
+            format_duration(baseline),
+            format!("{:+.1}%", change * 100.0),
+            match change {
+                _ if change > threshold => "REGRESSION",
+                _ if change < -threshold => "improved",
+                _ => "",
+            },
+            change > threshold,
+        ),
+    };
+
+    let row = format!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}  {}",
+        day,
+        measurement.stage.key(),
+        format_duration(measurement.median),
+        format_duration(measurement.p95),
+        baseline,
+        change,
+        verdict
+    );
+
+    println!("{}", row.trim_end());
+
+    regressed
+}
+
+fn record(baseline: &mut Document, day: u8, input_name: &str, measurements: &[Measurement]) {
+    let table = day_table(day);
+
+    baseline.insert(&table, "input", Value::String(input_name.to_string()));
+
+    for measurement in measurements {
+        baseline.insert(
+            &table,
+            &median_key(measurement.stage),
+            Value::Integer(measurement.median.as_nanos() as i128),
+        );
+        baseline.insert(
+            &table,
+            &p95_key(measurement.stage),
+            Value::Integer(measurement.p95.as_nanos() as i128),
+        );
+    }
+}
+
+pub fn bench(options: BenchOptions) -> Result<()> {
+    let days = match options.day {
+        Some(day) => vec![find_day(day)?],
+        None => DAYS.to_vec(),
+    };
+
+    // With --day, asking for a cache the day doesn't have is an error. Otherwise the days without
+    // one are parsed as usual.
+    if let (true, Some(day)) = (options.cache, options.day) {
+        find_cached_day(day)?;
+    }
+
+    let cache = InputCache::locate();
+
+    let baseline_path: &Path = &options.baseline;
+
+    let mut baseline = match baseline_path.exists() {
+        true => Document::read(baseline_path)?,
+        false => Document::default(),
+    };
+
+    let threshold = options.threshold / 100.0;
+
+    let mut regressions = 0;
+    let mut failures = 0;
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Day", "Stage", "Median", "p95", "Baseline", "Change"
+    );
+
+    for day in days {
+        let cached = match options.cache {
+            true => find_cached_day(day.number())
+                .ok()
+                .map(|cached_day| (cached_day, &cache)),
+            false => None,
+        };
+
+        let measured = day_input(day, &options.input).and_then(|(input, input_name)| {
+            Ok((bench_day(day, cached, &input, &options)?, input_name))
+        });
+
+        let (measurements, input_name) = match measured {
+            Ok(measured) => measured,
+            Err(error) => {
+                failures += 1;
+                println!("{:>3}  failed: {}", day.number(), error);
+                continue;
+            }
+        };
+
+        for measurement in measurements.iter() {
+            let comparison = compare(&baseline, day.number(), &input_name, measurement);
+
+            if print_measurement(day.number(), measurement, &comparison, threshold) {
+                regressions += 1;
+            }
+        }
+
+        if options.save {
+            record(&mut baseline, day.number(), &input_name, &measurements);
+        }
+    }
+
+    if options.save {
+        fs::write(baseline_path, baseline.to_string())
+            .map_err(|error| format!("Could not write {}: {}", baseline_path.display(), error))?;
+
+        println!("Saved the baseline in {}", baseline_path.display());
+    }
+
+    match (regressions, failures) {
+        (0, 0) => Ok(()),
+        (0, _) => Err(format!("{} days could not be benchmarked", failures).into()),
+        _ => Err(format!(
+            "{} stages are more than {}% slower than the baseline",
+            regressions, options.threshold
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(samples: &[u64]) -> Measurement {
+        Measurement::from_samples(
+            Stage::Parse,
+            samples
+                .iter()
+                .map(|&nanos| Duration::from_nanos(nanos))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn takes_nearest_rank_percentiles() {
+        let measurement = measure(&(1..=20).rev().collect::<Vec<_>>());
+
+        assert_eq!(measurement.median, Duration::from_nanos(10));
+        assert_eq!(measurement.p95, Duration::from_nanos(19));
+    }
+
+    #[test]
+    fn measures_single_samples() {
+        let measurement = measure(&[7]);
+
+        assert_eq!(measurement.median, Duration::from_nanos(7));
+        assert_eq!(measurement.p95, Duration::from_nanos(7));
+    }
+
+    #[test]
+    fn compares_medians_with_the_baseline_for_the_same_input() {
+        let mut baseline = Document::default();
+        record(
+            &mut baseline,
+            8,
+            "08_treetop_tree_house/input.txt",
+            &[measure(&[100])],
+        );
+
+        let measurement = measure(&[125]);
+
+        assert!(matches!(
+            compare(&baseline, 8, "08_treetop_tree_house/input.txt", &measurement),
+            Comparison::Change { change, .. } if (change - 0.25).abs() < 1e-9
+        ));
+        assert!(matches!(
+            compare(&baseline, 8, "generated (seed 1, scale 1)", &measurement),
+            Comparison::OtherInput
+        ));
+        assert!(matches!(
+            compare(&baseline, 9, "09_rope_bridge/input.txt", &measurement),
+            Comparison::Missing
+        ));
+    }
+}
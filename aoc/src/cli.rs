@@ -0,0 +1,443 @@
+use std::{
+    env::Args,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::{answer::Part, input::workspace_root, toml::parse_value, Result};
+
+use crate::{days::DAYS, output::Format, pool::default_thread_count, settings::SettingOverride};
+
+pub const USAGE: &str = "\
+Usage: aoc run --day <DAY> [--part <PART>] [--input <PATH | ->] [--stream | --cache]
+               [--format <text | json>] [--threads <COUNT>]
+               [--config <PATH>] [--<SETTING> <VALUE>]...
+       aoc run --all [--cache] [--format <text | json>] [--threads <COUNT>]
+               [--config <PATH>] [--<SETTING> <VALUE>]...
+       aoc watch --day <DAY> [--input <PATH>] [--poll] [--threads <COUNT>]
+                 [--config <PATH>] [--<SETTING> <VALUE>]...
+       aoc verify [--answers <PATH>] [--record]
+       aoc generate --day <DAY> [--seed <SEED>] [--scale <SCALE>]
+       aoc fuzz [--day <DAY>] [--seed <SEED>] [--iterations <COUNT>]
+       aoc new <DAY> <NAME>
+       aoc bench [--day <DAY>] [--input <PATH | -> | --scale <SCALE> [--seed <SEED>]]
+                 [--cache] [--warmup <COUNT>] [--iterations <COUNT>]
+                 [--baseline <PATH>] [--save] [--threshold <PERCENT>]
+       aoc compare [--day <DAY>] [--input <PATH | -> | --scale <SCALE> [--seed <SEED>]]
+                   [--iterations <COUNT>]";
+
+pub enum Command {
+    Run(RunOptions),
+    Watch(WatchOptions),
+    Verify(VerifyOptions),
+    Generate(GenerateOptions),
+    Fuzz(FuzzOptions),
+    Bench(BenchOptions),
+    Compare(CompareOptions),
+    New(NewOptions),
+}
+
+pub enum BenchInput {
+    Resolved(Option<String>),
+    Generated { seed: u64, scale: usize },
+}
+
+pub struct BenchOptions {
+    pub day: Option<u8>,
+    pub input: BenchInput,
+    pub cache: bool,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub baseline: PathBuf,
+    pub save: bool,
+    pub threshold: f64,
+}
+
+pub struct CompareOptions {
+    pub day: Option<u8>,
+    pub input: BenchInput,
+    pub iterations: usize,
+}
+
+pub struct NewOptions {
+    pub day: u8,
+    pub name: String,
+}
+
+pub struct GenerateOptions {
+    pub day: u8,
+    pub seed: u64,
+    pub scale: usize,
+}
+
+pub struct FuzzOptions {
+    pub day: Option<u8>,
+    pub seed: u64,
+    pub iterations: usize,
+}
+
+pub struct WatchOptions {
+    pub day: u8,
+    pub input: Option<String>,
+    pub poll: bool,
+    pub threads: usize,
+    pub config: Option<PathBuf>,
+    pub overrides: Vec<SettingOverride>,
+}
+
+pub struct VerifyOptions {
+    pub answers: PathBuf,
+    pub record: bool,
+}
+
+pub struct RunOptions {
+    pub target: RunTarget,
+    /// Whether to go through the parsed input cache, for the days which have one.
+    pub cache: bool,
+    pub format: Format,
+    pub threads: usize,
+    pub config: Option<PathBuf>,
+    pub overrides: Vec<SettingOverride>,
+}
+
+pub enum RunTarget {
+    Day {
+        day: u8,
+        part: Option<Part>,
+        input: Option<String>,
+        stream: bool,
+    },
+    All,
+}
+
+impl Command {
+    pub fn parse(mut args: Args) -> Result<Self> {
+        args.next();
+
+        match args.next().as_deref() {
+            Some("run") => Ok(Self::Run(RunOptions::parse(args)?)),
+            Some("watch") => Ok(Self::Watch(WatchOptions::parse(args)?)),
+            Some("verify") => Ok(Self::Verify(VerifyOptions::parse(args)?)),
+            Some("generate") => Ok(Self::Generate(GenerateOptions::parse(args)?)),
+            Some("fuzz") => Ok(Self::Fuzz(FuzzOptions::parse(args)?)),
+            Some("bench") => Ok(Self::Bench(BenchOptions::parse(args)?)),
+            Some("compare") => Ok(Self::Compare(CompareOptions::parse(args)?)),
+            Some("new") => Ok(Self::New(NewOptions::parse(args)?)),
+            Some(command) => Err(format!("Unknown command \"{}\"\n\n{}", command, USAGE).into()),
+            None => Err(USAGE.into()),
+        }
+    }
+}
+
+impl RunOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut all = false;
+        let mut stream = false;
+        let mut cache = false;
+        let mut format = Format::Text;
+        let mut threads = default_thread_count();
+        let mut config = None;
+        let mut overrides = vec![];
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--part" => part = Some(flag_value(&arg, args.next())?.parse::<Part>()?),
+                "--threads" => threads = flag_value(&arg, args.next())?.parse::<usize>()?,
+                "--input" => input = Some(flag_value(&arg, args.next())?),
+                "--all" => all = true,
+                "--stream" => stream = true,
+                "--cache" => cache = true,
+                "--format" => format = flag_value(&arg, args.next())?.parse::<Format>()?,
+                "--config" => config = Some(PathBuf::from(flag_value(&arg, args.next())?)),
+                _ if is_setting_flag(&arg) => overrides.push(setting_override(&arg, args.next())?),
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        let target = match (all, day) {
+            (true, None) if part.is_none() && input.is_none() && !stream => RunTarget::All,
+            (true, _) => {
+                return Err(
+                    "--all can't be combined with --day, --part, --input or --stream".into(),
+                )
+            }
+            (false, Some(day)) => RunTarget::Day {
+                day,
+                part,
+                input,
+                stream,
+            },
+            (false, None) => {
+                return Err(format!("Either --day or --all is required\n\n{}", USAGE).into())
+            }
+        };
+
+        if stream && cache {
+            return Err(
+                "--stream doesn't parse the input, so it can't be combined with --cache".into(),
+            );
+        }
+
+        if threads == 0 {
+            return Err("--threads should be at least 1".into());
+        }
+
+        Ok(Self {
+            target,
+            cache,
+            format,
+            threads,
+            config,
+            overrides,
+        })
+    }
+}
+
+impl WatchOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut input = None;
+        let mut poll = false;
+        let mut threads = default_thread_count();
+        let mut config = None;
+        let mut overrides = vec![];
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--input" => input = Some(flag_value(&arg, args.next())?),
+                "--poll" => poll = true,
+                "--threads" => threads = flag_value(&arg, args.next())?.parse::<usize>()?,
+                "--config" => config = Some(PathBuf::from(flag_value(&arg, args.next())?)),
+                _ if is_setting_flag(&arg) => overrides.push(setting_override(&arg, args.next())?),
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        let day = day.ok_or_else(|| format!("--day is required\n\n{}", USAGE))?;
+
+        if threads == 0 {
+            return Err("--threads should be at least 1".into());
+        }
+
+        Ok(Self {
+            day,
+            input,
+            poll,
+            threads,
+            config,
+            overrides,
+        })
+    }
+}
+
+impl VerifyOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut answers = workspace_root().join("answers.toml");
+        let mut record = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--answers" => answers = PathBuf::from(flag_value(&arg, args.next())?),
+                "--record" => record = true,
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        Ok(Self { answers, record })
+    }
+}
+
+impl NewOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let usage = || format!("aoc new takes a day and a name\n\n{}", USAGE);
+
+        let day = args.next().ok_or_else(usage)?.parse::<u8>()?;
+        let name = args.next().ok_or_else(usage)?;
+
+        if let Some(arg) = args.next() {
+            return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into());
+        }
+
+        Ok(Self { day, name })
+    }
+}
+
+impl GenerateOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut seed = default_seed();
+        let mut scale = 1;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--seed" => seed = flag_value(&arg, args.next())?.parse::<u64>()?,
+                "--scale" => scale = flag_value(&arg, args.next())?.parse::<usize>()?,
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        let day = day.ok_or_else(|| format!("--day is required\n\n{}", USAGE))?;
+
+        if scale == 0 {
+            return Err("--scale should be at least 1".into());
+        }
+
+        Ok(Self { day, seed, scale })
+    }
+}
+
+impl FuzzOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut seed = default_seed();
+        let mut iterations = 1000;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--seed" => seed = flag_value(&arg, args.next())?.parse::<u64>()?,
+                "--iterations" => iterations = flag_value(&arg, args.next())?.parse::<usize>()?,
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        Ok(Self {
+            day,
+            seed,
+            iterations,
+        })
+    }
+}
+
+impl BenchInput {
+    fn new(
+        day: Option<u8>,
+        input: Option<String>,
+        scale: Option<usize>,
+        seed: u64,
+    ) -> Result<Self> {
+        match (input, scale) {
+            (Some(_), Some(_)) => Err("--input can't be combined with --scale".into()),
+            (Some(_), None) if day.is_none() => Err("--input requires --day".into()),
+            (_, Some(0)) => Err("--scale should be at least 1".into()),
+            (input, None) => Ok(Self::Resolved(input)),
+            (None, Some(scale)) => Ok(Self::Generated { seed, scale }),
+        }
+    }
+}
+
+impl BenchOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut input = None;
+        let mut scale = None;
+        let mut seed = 2022;
+        let mut cache = false;
+        let mut warmup = 3;
+        let mut iterations = 20;
+        let mut baseline = workspace_root().join("bench-baseline.toml");
+        let mut save = false;
+        let mut threshold = 10.0;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--input" => input = Some(flag_value(&arg, args.next())?),
+                "--scale" => scale = Some(flag_value(&arg, args.next())?.parse::<usize>()?),
+                "--seed" => seed = flag_value(&arg, args.next())?.parse::<u64>()?,
+                "--cache" => cache = true,
+                "--warmup" => warmup = flag_value(&arg, args.next())?.parse::<usize>()?,
+                "--iterations" => iterations = flag_value(&arg, args.next())?.parse::<usize>()?,
+                "--baseline" => baseline = PathBuf::from(flag_value(&arg, args.next())?),
+                "--save" => save = true,
+                "--threshold" => threshold = flag_value(&arg, args.next())?.parse::<f64>()?,
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        let input = BenchInput::new(day, input, scale, seed)?;
+
+        if iterations == 0 {
+            return Err("--iterations should be at least 1".into());
+        }
+
+        Ok(Self {
+            day,
+            input,
+            cache,
+            warmup,
+            iterations,
+            baseline,
+            save,
+            threshold,
+        })
+    }
+}
+
+impl CompareOptions {
+    fn parse(mut args: Args) -> Result<Self> {
+        let mut day = None;
+        let mut input = None;
+        let mut scale = None;
+        let mut seed = 2022;
+        let mut iterations = 10;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => day = Some(flag_value(&arg, args.next())?.parse::<u8>()?),
+                "--input" => input = Some(flag_value(&arg, args.next())?),
+                "--scale" => scale = Some(flag_value(&arg, args.next())?.parse::<usize>()?),
+                "--seed" => seed = flag_value(&arg, args.next())?.parse::<u64>()?,
+                "--iterations" => iterations = flag_value(&arg, args.next())?.parse::<usize>()?,
+                _ => return Err(format!("Unknown argument \"{}\"\n\n{}", arg, USAGE).into()),
+            }
+        }
+
+        let input = BenchInput::new(day, input, scale, seed)?;
+
+        if iterations == 0 {
+            return Err("--iterations should be at least 1".into());
+        }
+
+        Ok(Self {
+            day,
+            input,
+            iterations,
+        })
+    }
+}
+
+fn default_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<String> {
+    value.ok_or_else(|| format!("{} should be followed by a value", flag).into())
+}
+
+fn setting_key(flag: &str) -> Option<String> {
+    flag.strip_prefix("--").map(|name| name.replace('-', "_"))
+}
+
+/// Whether `flag` is the `--kebab-case` name of some day's setting.
+fn is_setting_flag(flag: &str) -> bool {
+    setting_key(flag).is_some_and(|key| {
+        DAYS.iter()
+            .any(|day| day.setting_keys().contains(&key.as_str()))
+    })
+}
+
+fn setting_override(flag: &str, value: Option<String>) -> Result<SettingOverride> {
+    let value = flag_value(flag, value)?;
+    let value = parse_value(&value).map_err(|error| format!("Invalid {}:\n{}", flag, error))?;
+
+    Ok((setting_key(flag).unwrap_or_default(), value))
+}
@@ -0,0 +1,135 @@
+use std::{any::Any, time::Duration};
+
+use aoc_common::{
+    answer::{Answer, Part},
+    toml::Table,
+    variants::agree,
+    Result,
+};
+
+use crate::{
+    bench::{day_input, percentile, time},
+    cli::CompareOptions,
+    days::{find_day, find_variant_day, VariantDay, VARIANT_DAYS},
+    output::format_duration,
+    PARTS,
+};
+
+struct SolverRun {
+    name: &'static str,
+    answer: Result<Answer>,
+    median: Duration,
+}
+
+/// Runs every solver of `part` once for its answer, then `iterations` more times for its timing.
+fn run_solvers(
+    day: &dyn VariantDay,
+    parsed_input: &dyn Any,
+    config: &dyn Any,
+    part: Part,
+    iterations: usize,
+) -> Vec<SolverRun> {
+    day.solver_names(part)
+        .into_iter()
+        .enumerate()
+        .map(|(solver, name)| {
+            let solve = || day.solve_with(parsed_input, config, part, solver);
+
+            let answer = solve();
+
+            let mut samples = (0..iterations).map(|_| time(solve).1).collect::<Vec<_>>();
+
+            samples.sort_unstable();
+
+            SolverRun {
+                name,
+                answer,
+                median: percentile(&samples, 50),
+            }
+        })
+        .collect()
+}
+
+/// Prints how `run` did next to the reference of its part, telling whether they agree.
+fn print_run(day: u8, part: Part, run: &SolverRun, reference: &SolverRun) -> bool {
+    let answer = match &run.answer {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("failed: {}", error),
+    };
+
+    let agrees = agree(&reference.answer, &run.answer);
+
+    let row = format!(
+        "{:>3}  {:>4}  {:<22}  {:>10}  {:>7}  {}{}",
+        day,
+        part,
+        run.name,
+        format_duration(run.median),
+        format!(
+            "{:.2}x",
+            reference.median.as_secs_f64() / run.median.as_secs_f64().max(1e-9)
+        ),
+        answer.lines().next().unwrap_or_default(),
+        if agrees { "" } else { "  MISMATCH" }
+    );
+
+    println!("{}", row.trim_end());
+
+    agrees
+}
+
+/// Runs every solver of the days which have more than one, checking that they all agree with the
+/// reference of their part and timing them side by side.
+pub fn compare(options: CompareOptions) -> Result<()> {
+    let days = match options.day {
+        Some(day) => vec![find_variant_day(day)?],
+        None => VARIANT_DAYS.to_vec(),
+    };
+
+    let mut mismatches = 0;
+    let mut failures = 0;
+
+    println!(
+        "{:>3}  {:>4}  {:<22}  {:>10}  {:>7}  Answer",
+        "Day", "Part", "Solver", "Median", "Speedup"
+    );
+
+    for variant_day in days {
+        let day = find_day(variant_day.number())?;
+
+        // Like bench, with the puzzle's own constants.
+        let parsed = day_input(day, &options.input)
+            .and_then(|(input, _)| Ok((day.parse(&input)?, day.configure(&Table::new())?)));
+
+        let (parsed_input, config) = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                failures += 1;
+                println!("{:>3}  failed: {}", day.number(), error);
+                continue;
+            }
+        };
+
+        for part in PARTS {
+            let runs = run_solvers(
+                variant_day,
+                parsed_input.as_ref(),
+                config.as_ref(),
+                part,
+                options.iterations,
+            );
+
+            for run in runs.iter() {
+                if !print_run(day.number(), part, run, &runs[0]) {
+                    mismatches += 1;
+                }
+            }
+        }
+    }
+
+    match (mismatches, failures) {
+        (0, 0) => Ok(()),
+        (0, _) => Err(format!("{} days could not be compared", failures).into()),
+        _ => Err(format!("{} solvers disagree with their reference", mismatches).into()),
+    }
+}
@@ -0,0 +1,379 @@
+use std::{any::Any, io::BufRead, marker::PhantomData};
+
+use aoc_common::{
+    answer::{Answer, Part},
+    cache::{CacheStatus, CachedSolution, InputCache},
+    config::Config,
+    generator::{fuzz, FuzzFailure, Generator},
+    input::InputSource,
+    random::Rng,
+    stream::StreamingSolution,
+    toml::Table,
+    variants::{SolutionVariants, Solver},
+    Result, Solution,
+};
+
+/// The table of `day` in the workspace's TOML files, e.g. `[day07]` in `aoc.toml` and
+/// `answers.toml`.
+pub fn day_table(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+pub trait Day: Sync {
+    fn number(&self) -> u8;
+
+    fn directory(&self) -> String;
+
+    fn setting_keys(&self) -> &'static [&'static str];
+
+    fn configure(&self, settings: &Table) -> Result<Box<dyn Any + Send + Sync>>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>>;
+
+    fn solve(&self, parsed_input: &dyn Any, config: &dyn Any, part: Part) -> Result<Answer>;
+
+    fn generate(&self, seed: u64, scale: usize) -> String;
+
+    fn fuzz(&self, seed: u64, iterations: usize) -> Result<(), FuzzFailure>;
+
+    fn input_source(&self, argument: Option<&str>) -> InputSource {
+        InputSource::resolve_for_directory(&self.directory(), argument)
+    }
+}
+
+struct SolutionDay<S>(PhantomData<S>);
+
+fn downcast_config<S: Solution>(config: &dyn Any) -> Result<&S::Config> {
+    Ok(config
+        .downcast_ref::<S::Config>()
+        .ok_or_else(|| format!("Day {} was given another day's settings", S::DAY))?)
+}
+
+fn downcast_input<S>(parsed_input: &dyn Any) -> Result<&S::Input>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    Ok(parsed_input
+        .downcast_ref::<S::Input>()
+        .ok_or_else(|| format!("Day {} was given input parsed by another day", S::DAY))?)
+}
+
+impl<S> Day for SolutionDay<S>
+where
+    S: Solution + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn number(&self) -> u8 {
+        S::DAY
+    }
+
+    fn directory(&self) -> String {
+        S::directory()
+    }
+
+    fn setting_keys(&self) -> &'static [&'static str] {
+        S::Config::KEYS
+    }
+
+    fn configure(&self, settings: &Table) -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(S::Config::from_table(settings)?))
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed_input: &dyn Any, config: &dyn Any, part: Part) -> Result<Answer> {
+        let parsed_input = downcast_input::<S>(parsed_input)?;
+        let config = downcast_config::<S>(config)?;
+
+        match part {
+            Part::One => S::part_one_with(parsed_input, config).map(Into::into),
+            Part::Two => S::part_two_with(parsed_input, config).map(Into::into),
+        }
+    }
+
+    fn generate(&self, seed: u64, scale: usize) -> String {
+        S::Generator::scaled(scale).generate(&mut Rng::new(seed))
+    }
+
+    fn fuzz(&self, seed: u64, iterations: usize) -> Result<(), FuzzFailure> {
+        fuzz::<S>(seed, iterations)
+    }
+}
+
+pub trait StreamingDay: Sync {
+    fn number(&self) -> u8;
+
+    fn summarize(
+        &self,
+        reader: &mut dyn BufRead,
+        config: &dyn Any,
+    ) -> Result<Box<dyn Any + Send + Sync>>;
+
+    fn solve(&self, summary: &dyn Any, config: &dyn Any, part: Part) -> Result<Answer>;
+}
+
+struct StreamingSolutionDay<S>(PhantomData<S>);
+
+impl<S> StreamingDay for StreamingSolutionDay<S>
+where
+    S: StreamingSolution + Sync,
+    S::Summary: Send + Sync + 'static,
+{
+    fn number(&self) -> u8 {
+        S::DAY
+    }
+
+    fn summarize(
+        &self,
+        reader: &mut dyn BufRead,
+        config: &dyn Any,
+    ) -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(S::summarize_stream(
+            reader,
+            downcast_config::<S>(config)?,
+        )?))
+    }
+
+    fn solve(&self, summary: &dyn Any, config: &dyn Any, part: Part) -> Result<Answer> {
+        let summary = summary
+            .downcast_ref::<S::Summary>()
+            .ok_or_else(|| format!("Day {} was given another day's summary", S::DAY))?;
+        let config = downcast_config::<S>(config)?;
+
+        match part {
+            Part::One => S::part_one_from_summary(summary, config).map(Into::into),
+            Part::Two => S::part_two_from_summary(summary, config).map(Into::into),
+        }
+    }
+}
+
+pub trait CachedDay: Sync {
+    fn number(&self) -> u8;
+
+    /// Parses the input like `Day::parse`, going through `cache` first.
+    fn parse(
+        &self,
+        cache: &InputCache,
+        input: &str,
+    ) -> Result<(Box<dyn Any + Send + Sync>, CacheStatus)>;
+}
+
+struct CachedSolutionDay<S>(PhantomData<S>);
+
+impl<S> CachedDay for CachedSolutionDay<S>
+where
+    S: CachedSolution + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn number(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(
+        &self,
+        cache: &InputCache,
+        input: &str,
+    ) -> Result<(Box<dyn Any + Send + Sync>, CacheStatus)> {
+        let (parsed_input, status) = cache.parse::<S>(input)?;
+
+        Ok((Box::new(parsed_input), status))
+    }
+}
+
+pub trait VariantDay: Sync {
+    fn number(&self) -> u8;
+
+    /// The names of the solvers of `part`, its reference first.
+    fn solver_names(&self, part: Part) -> Vec<&'static str>;
+
+    /// Solves `part` like `Day::solve`, with its `solver`th solver.
+    fn solve_with(
+        &self,
+        parsed_input: &dyn Any,
+        config: &dyn Any,
+        part: Part,
+        solver: usize,
+    ) -> Result<Answer>;
+}
+
+struct VariantSolutionDay<S>(PhantomData<S>);
+
+fn run_solver<S: Solution, T: Into<Answer>>(
+    solvers: Vec<Solver<S, T>>,
+    solver: usize,
+    parsed_input: &S::Input,
+    config: &S::Config,
+) -> Result<Answer> {
+    let solver = solvers
+        .get(solver)
+        .ok_or_else(|| format!("Day {} has no solver #{}", S::DAY, solver))?;
+
+    (solver.solve)(parsed_input, config).map(Into::into)
+}
+
+impl<S> VariantDay for VariantSolutionDay<S>
+where
+    S: SolutionVariants + Sync,
+    S::Input: 'static,
+{
+    fn number(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solver_names(&self, part: Part) -> Vec<&'static str> {
+        match part {
+            Part::One => S::part_one_solvers()
+                .iter()
+                .map(|solver| solver.name)
+                .collect(),
+            Part::Two => S::part_two_solvers()
+                .iter()
+                .map(|solver| solver.name)
+                .collect(),
+        }
+    }
+
+    fn solve_with(
+        &self,
+        parsed_input: &dyn Any,
+        config: &dyn Any,
+        part: Part,
+        solver: usize,
+    ) -> Result<Answer> {
+        let parsed_input = downcast_input::<S>(parsed_input)?;
+        let config = downcast_config::<S>(config)?;
+
+        match part {
+            Part::One => run_solver(S::part_one_solvers(), solver, parsed_input, config),
+            Part::Two => run_solver(S::part_two_solvers(), solver, parsed_input, config),
+        }
+    }
+}
+
+macro_rules! days {
+    ($($solution:ty),* $(,)?) => {
+        [$(&SolutionDay::<$solution>(PhantomData) as &dyn Day),*]
+    };
+}
+
+pub static DAYS: &[&dyn Day] = &days![
+    calorie_counting::CalorieCounting,
+    rock_paper_scissors::RockPaperScissors,
+    rucksack_reorganization::RucksackReorganization,
+    camp_cleanup::CampCleanup,
+    supply_stacks::SupplyStacks,
+    tuning_trouble::TuningTrouble,
+    no_space_left_on_device::NoSpaceLeftOnDevice,
+    treetop_tree_house::TreetopTreeHouse,
+    rope_bridge::RopeBridge,
+    cathode_ray_tube::CathodeRayTube,
+];
+
+macro_rules! streaming_days {
+    ($($solution:ty),* $(,)?) => {
+        [$(&StreamingSolutionDay::<$solution>(PhantomData) as &dyn StreamingDay),*]
+    };
+}
+
+pub static STREAMING_DAYS: [&dyn StreamingDay; 5] = streaming_days![
+    calorie_counting::CalorieCounting,
+    rock_paper_scissors::RockPaperScissors,
+    camp_cleanup::CampCleanup,
+    tuning_trouble::TuningTrouble,
+    cathode_ray_tube::CathodeRayTube,
+];
+
+macro_rules! cached_days {
+    ($($solution:ty),* $(,)?) => {
+        [$(&CachedSolutionDay::<$solution>(PhantomData) as &dyn CachedDay),*]
+    };
+}
+
+pub static CACHED_DAYS: [&dyn CachedDay; 5] = cached_days![
+    camp_cleanup::CampCleanup,
+    supply_stacks::SupplyStacks,
+    no_space_left_on_device::NoSpaceLeftOnDevice,
+    treetop_tree_house::TreetopTreeHouse,
+    cathode_ray_tube::CathodeRayTube,
+];
+
+macro_rules! variant_days {
+    ($($solution:ty),* $(,)?) => {
+        [$(&VariantSolutionDay::<$solution>(PhantomData) as &dyn VariantDay),*]
+    };
+}
+
+pub static VARIANT_DAYS: [&dyn VariantDay; 2] = variant_days![
+    tuning_trouble::TuningTrouble,
+    treetop_tree_house::TreetopTreeHouse,
+];
+
+pub fn find_day(number: u8) -> Result<&'static dyn Day> {
+    DAYS.iter()
+        .find(|day| day.number() == number)
+        .copied()
+        .ok_or_else(|| format!("There is no solution for day {}", number).into())
+}
+
+pub fn find_streaming_day(number: u8) -> Result<&'static dyn StreamingDay> {
+    STREAMING_DAYS
+        .iter()
+        .find(|day| day.number() == number)
+        .copied()
+        .ok_or_else(|| {
+            let streaming_days = STREAMING_DAYS
+                .iter()
+                .map(|day| day.number().to_string())
+                .collect::<Vec<_>>();
+
+            format!(
+                "Day {} can't be streamed, only days {} can",
+                number,
+                streaming_days.join(", ")
+            )
+            .into()
+        })
+}
+
+pub fn find_cached_day(number: u8) -> Result<&'static dyn CachedDay> {
+    CACHED_DAYS
+        .iter()
+        .find(|day| day.number() == number)
+        .copied()
+        .ok_or_else(|| {
+            let cached_days = CACHED_DAYS
+                .iter()
+                .map(|day| day.number().to_string())
+                .collect::<Vec<_>>();
+
+            format!(
+                "Day {} has no parsed input cache, only days {} have",
+                number,
+                cached_days.join(", ")
+            )
+            .into()
+        })
+}
+
+pub fn find_variant_day(number: u8) -> Result<&'static dyn VariantDay> {
+    VARIANT_DAYS
+        .iter()
+        .find(|day| day.number() == number)
+        .copied()
+        .ok_or_else(|| {
+            let variant_days = VARIANT_DAYS
+                .iter()
+                .map(|day| day.number().to_string())
+                .collect::<Vec<_>>();
+
+            format!(
+                "Day {} has a single solver per part, only days {} have more",
+                number,
+                variant_days.join(", ")
+            )
+            .into()
+        })
+}
@@ -0,0 +1,74 @@
+use std::{env, fs, panic};
+
+use aoc_common::Result;
+
+use crate::{
+    cli::{FuzzOptions, GenerateOptions},
+    days::{find_day, Day, DAYS},
+};
+
+pub fn generate(options: GenerateOptions) -> Result<()> {
+    let day = find_day(options.day)?;
+
+    eprintln!("Seed: {}", options.seed);
+    print!("{}", day.generate(options.seed, options.scale));
+
+    Ok(())
+}
+
+pub fn fuzz(options: FuzzOptions) -> Result<()> {
+    let days = match options.day {
+        Some(day) => vec![find_day(day)?],
+        None => DAYS.to_vec(),
+    };
+
+    println!("Seed: {}", options.seed);
+
+    // Panics are the expected outcome of a failed iteration and are reported below, so the
+    // default hook would only repeat them.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let failures = days
+        .iter()
+        .map(|day| fuzz_day(*day, &options))
+        .filter(|outcome| outcome.is_err())
+        .count();
+
+    panic::set_hook(default_hook);
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{} days panicked on mutated inputs", failures).into()),
+    }
+}
+
+fn fuzz_day(day: &dyn Day, options: &FuzzOptions) -> Result<()> {
+    let failure = match day.fuzz(options.seed, options.iterations) {
+        Ok(()) => {
+            println!(
+                "Day {:>2}: ok ({} mutated inputs)",
+                day.number(),
+                options.iterations
+            );
+            return Ok(());
+        }
+        Err(failure) => failure,
+    };
+
+    let input_path = env::temp_dir().join(format!(
+        "aoc-fuzz-day{:02}-{}-{}.txt",
+        failure.day, options.seed, failure.iteration
+    ));
+
+    fs::write(&input_path, &failure.input)?;
+
+    println!(
+        "Day {:>2}: {}\n        input saved to {}",
+        day.number(),
+        failure,
+        input_path.display()
+    );
+
+    Err(failure.into())
+}
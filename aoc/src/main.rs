@@ -0,0 +1,96 @@
+mod bench;
+mod cli;
+mod compare;
+mod days;
+mod fuzz;
+mod memory;
+mod output;
+mod pool;
+mod scaffold;
+mod settings;
+mod solve;
+mod verify;
+mod watch;
+
+use std::{env, process::ExitCode, time::Instant};
+
+use aoc_common::{answer::Part, Result};
+
+use cli::{Command, RunOptions, RunTarget, VerifyOptions};
+use pool::default_thread_count;
+use settings::Settings;
+use solve::{solve_all_days, solve_days, DayRequest};
+
+const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+fn run(options: RunOptions) -> Result<()> {
+    let settings = Settings::load(options.config.as_deref(), options.overrides)?;
+
+    let start = Instant::now();
+
+    let results = match options.target {
+        RunTarget::Day {
+            day,
+            part,
+            input,
+            stream,
+        } => {
+            let request = DayRequest {
+                day,
+                parts: part.map_or(PARTS.to_vec(), |part| vec![part]),
+                input: input.as_deref(),
+                stream,
+                cache: options.cache,
+            };
+
+            solve_days(&[request], &settings, options.threads)?
+        }
+        RunTarget::All => solve_all_days(&settings, options.threads, options.cache)?,
+    };
+
+    let wall_time = start.elapsed();
+
+    options.format.print_header();
+
+    for result in results.iter() {
+        options.format.print(result);
+    }
+
+    options
+        .format
+        .print_footer(&results, wall_time, options.threads);
+
+    match results.iter().all(|result| result.answer.is_ok()) {
+        true => Ok(()),
+        false => Err("Some parts could not be solved".into()),
+    }
+}
+
+fn main() -> ExitCode {
+    let result = Command::parse(env::args()).and_then(|command| match command {
+        Command::Run(options) => run(options),
+        Command::Watch(options) => watch::watch(options),
+        // The recorded answers are the puzzle's, so they are checked without any settings.
+        Command::Verify(VerifyOptions { answers, record }) => {
+            solve_all_days(&Settings::default(), default_thread_count(), false).and_then(
+                |results| match record {
+                    true => verify::record(&results, &answers),
+                    false => verify::verify(&results, &answers),
+                },
+            )
+        }
+        Command::Generate(options) => fuzz::generate(options),
+        Command::Fuzz(options) => fuzz::fuzz(options),
+        Command::Bench(options) => bench::bench(options),
+        Command::Compare(options) => compare::compare(options),
+        Command::New(options) => scaffold::new_day(options),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the bytes allocated by each thread, so a thread can tell how much memory a piece of
+/// work needed at most.
+struct TrackingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+    static PEAK_ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(change: isize) {
+    // Thread locals may be gone while a thread shuts down, and allocations then go uncounted.
+    let _ = ALLOCATED.try_with(|allocated| {
+        let now_allocated = allocated.get() + change;
+
+        allocated.set(now_allocated);
+
+        let _ = PEAK_ALLOCATED.try_with(|peak| peak.set(peak.get().max(now_allocated)));
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+
+        if !pointer.is_null() {
+            track(layout.size() as isize);
+        }
+
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+
+        if !pointer.is_null() {
+            track(layout.size() as isize);
+        }
+
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+
+        track(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+
+        if !new_pointer.is_null() {
+            track(new_size as isize - layout.size() as isize);
+        }
+
+        new_pointer
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The most bytes allocated at once.
+    pub peak: usize,
+    /// The bytes still allocated once done.
+    pub retained: usize,
+}
+
+/// Runs `work` and returns how much it allocated on this thread, on top of what was already
+/// allocated.
+pub fn measure<R>(work: impl FnOnce() -> R) -> (R, MemoryUsage) {
+    let allocated_before = ALLOCATED.with(Cell::get);
+    let outer_peak = PEAK_ALLOCATED.with(|peak| peak.replace(allocated_before));
+
+    let result = work();
+
+    let peak = PEAK_ALLOCATED.with(|peak| {
+        let work_peak = peak.get();
+
+        peak.set(work_peak.max(outer_peak));
+
+        work_peak
+    });
+
+    let usage = MemoryUsage {
+        peak: (peak - allocated_before).max(0) as usize,
+        retained: (ALLOCATED.with(Cell::get) - allocated_before).max(0) as usize,
+    };
+
+    (result, usage)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::*;
+
+    #[test]
+    fn measures_the_peak_of_nested_work() {
+        let ((_, inner_usage), outer_usage) = measure(|| {
+            drop(black_box(vec![0u8; 4096]));
+
+            measure(|| black_box(vec![0u8; 1024]).len())
+        });
+
+        assert!((1024..4096).contains(&inner_usage.peak));
+        assert_eq!(inner_usage.retained, 0);
+        assert!(outer_usage.peak >= 4096);
+    }
+
+    #[test]
+    fn measures_what_work_keeps() {
+        let (kept, usage) = measure(|| vec![0u64; 1000]);
+
+        assert!(usage.retained >= 8000);
+        assert!(usage.peak >= usage.retained);
+
+        drop(kept);
+    }
+
+    #[test]
+    fn formats_byte_counts() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
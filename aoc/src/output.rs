@@ -0,0 +1,188 @@
+use std::{str::FromStr, time::Duration};
+
+use aoc_common::{
+    answer::{Answer, Part},
+    cache::CacheStatus,
+    Error, Result,
+};
+
+use crate::memory::format_bytes;
+
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Result<Answer>,
+    /// Time spent reading and parsing the input, shared by every part of the day.
+    pub parse_elapsed: Duration,
+    /// Whether the parsed input came from the cache, when it was looked up there.
+    pub cache_status: Option<CacheStatus>,
+    /// Time spent solving the part alone.
+    pub elapsed: Duration,
+    /// The most memory allocated at once while reading the input and solving the part.
+    pub peak_memory: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(format_str: &str) -> Result<Self, Self::Err> {
+        match format_str {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("A format should be text or json. Got: {}", format_str).into()),
+        }
+    }
+}
+
+impl Format {
+    pub fn print_header(&self) {
+        match self {
+            Self::Text => println!(
+                "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  Answer",
+                "Day", "Part", "Parse", "Solve", "Memory"
+            ),
+            Self::Json => {}
+        }
+    }
+
+    pub fn print(&self, result: &PartResult) {
+        match self {
+            Self::Text => print_text(result),
+            Self::Json => println!("{}", json_record(result)),
+        }
+    }
+
+    pub fn print_footer(&self, results: &[PartResult], wall_time: Duration, threads: usize) {
+        match self {
+            Self::Text => {
+                let mut parse_time = Duration::ZERO;
+                let mut previous_day = None;
+
+                for result in results {
+                    if previous_day != Some(result.day) {
+                        parse_time += result.parse_elapsed;
+                        previous_day = Some(result.day);
+                    }
+                }
+
+                let solve_time = results.iter().map(|result| result.elapsed).sum();
+
+                println!(
+                    "\nParsing took {} and solving {}, done in {} on {} thread{}",
+                    format_duration(parse_time),
+                    format_duration(solve_time),
+                    format_duration(wall_time),
+                    threads,
+                    if threads == 1 { "" } else { "s" }
+                );
+
+                if results
+                    .iter()
+                    .any(|result| result.cache_status == Some(CacheStatus::Hit))
+                {
+                    println!("Parse times marked with * read the parsed input from the cache");
+                }
+            }
+            Self::Json => {}
+        }
+    }
+}
+
+fn print_text(result: &PartResult) {
+    let answer = match &result.answer {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("failed: {}", error),
+    };
+
+    let mut answer_lines = answer.lines();
+
+    // Marked in the gap after the column, so that cached rows stay aligned with the others.
+    let cache_marker = match result.cache_status {
+        Some(CacheStatus::Hit) => '*',
+        _ => ' ',
+    };
+
+    println!(
+        "{:>3}  {:>4}  {:>10}{} {:>10}  {:>10}  {}",
+        result.day,
+        result.part,
+        format_duration(result.parse_elapsed),
+        cache_marker,
+        format_duration(result.elapsed),
+        format_bytes(result.peak_memory),
+        answer_lines.next().unwrap_or_default()
+    );
+
+    for line in answer_lines {
+        println!(
+            "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {}",
+            "", "", "", "", "", line
+        );
+    }
+}
+
+fn json_record(result: &PartResult) -> String {
+    let outcome = match &result.answer {
+        Ok(Answer::Integer(value)) => format!("\"answer\":{}", value),
+        Ok(Answer::Text(value)) => format!("\"answer\":{}", json_string(value)),
+        Err(error) => format!("\"error\":{}", json_string(&error.to_string())),
+    };
+
+    let cache = match result.cache_status {
+        Some(CacheStatus::Hit) => ",\"cache\":\"hit\"",
+        Some(CacheStatus::Miss) => ",\"cache\":\"miss\"",
+        None => "",
+    };
+
+    format!(
+        "{{\"day\":{},\"part\":{},{},\"parse_ns\":{}{},\"elapsed_ns\":{},\"peak_memory_bytes\":{}}}",
+        result.day,
+        result.part,
+        outcome,
+        result.parse_elapsed.as_nanos(),
+        cache,
+        result.elapsed.as_nanos(),
+        result.peak_memory
+    )
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+
+    escaped.push('"');
+
+    for value_char in value.chars() {
+        match value_char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if control.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            _ => escaped.push(value_char),
+        }
+    }
+
+    escaped.push('"');
+
+    escaped
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+
+    match nanos {
+        _ if nanos < 1e3 => format!("{:.0} ns", nanos),
+        _ if nanos < 1e6 => format!("{:.1} µs", nanos / 1e3),
+        _ if nanos < 1e9 => format!("{:.2} ms", nanos / 1e6),
+        _ => format!("{:.2} s", nanos / 1e9),
+    }
+}
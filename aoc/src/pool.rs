@@ -0,0 +1,190 @@
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{Condvar, Mutex},
+    thread,
+};
+
+use aoc_common::{generator::panic_message, Result};
+
+type Job<'scope> = Box<dyn FnOnce(&WorkQueue<'scope>) + Send + 'scope>;
+
+#[derive(Default)]
+struct QueueState<'scope> {
+    jobs: VecDeque<Job<'scope>>,
+    running: usize,
+}
+
+/// Jobs waiting for a thread of the pool. Running jobs can queue more jobs, e.g. solving each part
+/// once the input is parsed.
+#[derive(Default)]
+pub struct WorkQueue<'scope> {
+    state: Mutex<QueueState<'scope>>,
+    changed: Condvar,
+}
+
+impl<'scope> WorkQueue<'scope> {
+    pub fn spawn(&self, job: impl FnOnce(&WorkQueue<'scope>) + Send + 'scope) {
+        self.state.lock().unwrap().jobs.push_back(Box::new(job));
+        self.changed.notify_one();
+    }
+
+    fn work(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                state.running += 1;
+                drop(state);
+
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| job(self)));
+
+                state = self.state.lock().unwrap();
+                state.running -= 1;
+                self.changed.notify_all();
+
+                // Other threads keep working, and the panic surfaces once they are done.
+                if let Err(panic) = outcome {
+                    drop(state);
+                    panic::resume_unwind(panic);
+                }
+            } else if state.running == 0 {
+                return;
+            } else {
+                state = self.changed.wait(state).unwrap();
+            }
+        }
+    }
+}
+
+/// Runs `job`, turning a panic into an error so that it only fails its own result rather than
+/// every job of the pool.
+pub fn catching_panics<T>(job: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(job))
+        .unwrap_or_else(|payload| Err(format!("Panicked: {}", panic_message(payload)).into()))
+}
+
+pub fn default_thread_count() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs the jobs `queue_jobs` queues, and any job they queue in turn, on `thread_count` threads,
+/// returning once all of them are done.
+pub fn run<'scope>(thread_count: usize, queue_jobs: impl FnOnce(&WorkQueue<'scope>)) {
+    let queue = WorkQueue::default();
+
+    queue_jobs(&queue);
+
+    thread::scope(|scope| {
+        for _ in 0..thread_count.max(1) {
+            scope.spawn(|| queue.work());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn runs_jobs_queued_by_other_jobs() {
+        let done = AtomicUsize::new(0);
+
+        run(4, |queue| {
+            for _ in 0..10 {
+                queue.spawn(|queue| {
+                    done.fetch_add(1, Ordering::SeqCst);
+
+                    for _ in 0..2 {
+                        queue.spawn(|_| {
+                            done.fetch_add(1, Ordering::SeqCst);
+                        });
+                    }
+                });
+            }
+        });
+
+        assert_eq!(done.load(Ordering::SeqCst), 30);
+    }
+
+    #[test]
+    fn runs_jobs_on_several_threads() {
+        let thread_ids = Mutex::new(Vec::new());
+        let waiting = AtomicUsize::new(0);
+
+        run(2, |queue| {
+            for _ in 0..2 {
+                queue.spawn(|_| {
+                    waiting.fetch_add(1, Ordering::SeqCst);
+
+                    // Both jobs only finish once both started, so they must run side by side.
+                    while waiting.load(Ordering::SeqCst) < 2 {
+                        thread::yield_now();
+                    }
+
+                    thread_ids.lock().unwrap().push(thread::current().id());
+                });
+            }
+        });
+
+        let thread_ids = thread_ids.into_inner().unwrap();
+
+        assert_ne!(thread_ids[0], thread_ids[1]);
+    }
+
+    #[test]
+    fn finishes_the_other_jobs_when_one_panics() {
+        let done = AtomicUsize::new(0);
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            run(2, |queue| {
+                queue.spawn(|_| panic!("job failed"));
+
+                for _ in 0..10 {
+                    queue.spawn(|_| {
+                        done.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+            })
+        }));
+
+        assert!(outcome.is_err());
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn reports_a_panicking_job_as_an_error_next_to_the_others() {
+        let results = Mutex::new(Vec::new());
+
+        run(2, |queue| {
+            queue.spawn(|_| {
+                let result = catching_panics(|| -> Result<usize> { panic!("job failed") });
+
+                results
+                    .lock()
+                    .unwrap()
+                    .push(result.map_err(|error| error.to_string()));
+            });
+            queue.spawn(|_| {
+                let result = catching_panics(|| Ok(1));
+
+                results
+                    .lock()
+                    .unwrap()
+                    .push(result.map_err(|error| error.to_string()));
+            });
+        });
+
+        let mut results = results.into_inner().unwrap();
+
+        results.sort();
+
+        assert_eq!(
+            results,
+            vec![Ok(1), Err("Panicked: job failed".to_string())]
+        );
+    }
+}
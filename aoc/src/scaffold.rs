@@ -0,0 +1,324 @@
+use std::{env, fs, path::Path, process};
+
+use aoc_common::{input::workspace_root, Result};
+
+use crate::{cli::NewOptions, days::find_day};
+
+/// The files of a new day, relative to its directory, with the template they are rendered from.
+const TEMPLATES: [(&str, &str); 6] = [
+    (
+        "Cargo.toml",
+        include_str!("../templates/day/Cargo.toml.template"),
+    ),
+    (
+        "src/lib.rs",
+        include_str!("../templates/day/lib.rs.template"),
+    ),
+    (
+        "src/main.rs",
+        include_str!("../templates/day/main.rs.template"),
+    ),
+    (
+        "src/generator.rs",
+        include_str!("../templates/day/generator.rs.template"),
+    ),
+    (
+        "fixtures/example.txt",
+        include_str!("../templates/day/example.txt.template"),
+    ),
+    (
+        ".gitignore",
+        include_str!("../templates/day/gitignore.template"),
+    ),
+];
+
+type Register = fn(&Scaffold, &str) -> Result<String>;
+
+/// The workspace files a new day is registered in, relative to the workspace root.
+const REGISTRATIONS: [(&str, Register); 3] = [
+    ("Cargo.toml", Scaffold::register_member),
+    ("aoc/Cargo.toml", Scaffold::register_dependency),
+    ("aoc/src/days.rs", Scaffold::register_day),
+];
+
+struct Scaffold {
+    day: u8,
+    name: String,
+}
+
+impl Scaffold {
+    fn new(day: u8, name: &str) -> Result<Self> {
+        if !(1..=25).contains(&day) {
+            return Err(format!("There is no day {} in Advent of Code", day).into());
+        }
+
+        let is_snake_case = name.starts_with(|name_char: char| name_char.is_ascii_lowercase())
+            && name.chars().all(|name_char| {
+                name_char.is_ascii_lowercase() || name_char.is_ascii_digit() || name_char == '_'
+            })
+            && !name.ends_with('_')
+            && !name.contains("__");
+
+        if !is_snake_case {
+            return Err(format!(
+                "\"{}\" should be the puzzle title in snake_case, like monkey_in_the_middle",
+                name
+            )
+            .into());
+        }
+
+        Ok(Self {
+            day,
+            name: name.to_string(),
+        })
+    }
+
+    fn directory(&self) -> String {
+        format!("{:02}_{}", self.day, self.name)
+    }
+
+    fn type_name(&self) -> String {
+        self.name
+            .split('_')
+            .map(|word| {
+                let (first, rest) = word.split_at(1);
+
+                first.to_ascii_uppercase() + rest
+            })
+            .collect()
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{day}}", &self.day.to_string())
+            .replace("{{name}}", &self.name)
+            .replace("{{type}}", &self.type_name())
+    }
+
+    /// Adds the crate to the workspace members, after the last day.
+    fn register_member(&self, manifest: &str) -> Result<String> {
+        let members_end = manifest
+            .find("members = [")
+            .and_then(|members_start| {
+                manifest[members_start..]
+                    .find("\n]")
+                    .map(|end| members_start + end + 1)
+            })
+            .ok_or("Could not find the workspace members in Cargo.toml")?;
+
+        Ok(format!(
+            "{}    \"{}\",\n{}",
+            &manifest[..members_end],
+            self.directory(),
+            &manifest[members_end..]
+        ))
+    }
+
+    /// Adds the crate to the runner's dependencies, after the last day.
+    fn register_dependency(&self, manifest: &str) -> Result<String> {
+        if !manifest.trim_end().ends_with('}') || !manifest.contains("[dependencies]") {
+            return Err("The dependencies should be the last table of aoc/Cargo.toml".into());
+        }
+
+        Ok(format!(
+            "{}\n{} = {{ path = \"../{}\" }}\n",
+            manifest.trim_end(),
+            self.name,
+            self.directory()
+        ))
+    }
+
+    /// Adds the solution to the runner's `DAYS`, after the last day.
+    fn register_day(&self, days_source: &str) -> Result<String> {
+        let days_end = days_source
+            .find("pub static DAYS")
+            .and_then(|days_start| {
+                days_source[days_start..]
+                    .find("\n];")
+                    .map(|end| days_start + end + 1)
+            })
+            .ok_or("Could not find DAYS in aoc/src/days.rs")?;
+
+        Ok(format!(
+            "{}    {}::{},\n{}",
+            &days_source[..days_end],
+            self.name,
+            self.type_name(),
+            &days_source[days_end..]
+        ))
+    }
+}
+
+fn format_crate(path: &Path) -> Result<()> {
+    let status = process::Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .args(["fmt", "--manifest-path"])
+        .arg(path)
+        .status()?;
+
+    match status.success() {
+        true => Ok(()),
+        false => Err("cargo fmt failed on the new day".into()),
+    }
+}
+
+fn write_templates(scaffold: &Scaffold, directory: &Path) -> Result<()> {
+    for (file, template) in TEMPLATES {
+        let path = directory.join(file);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, scaffold.render(template))?;
+    }
+
+    Ok(())
+}
+
+/// Creates the crate of a new day from the templates and registers it with the workspace and the
+/// runner. Everything is checked before the first file is written.
+pub fn new_day(options: NewOptions) -> Result<()> {
+    let scaffold = Scaffold::new(options.day, &options.name)?;
+
+    if find_day(scaffold.day).is_ok() {
+        return Err(format!("Day {} already has a solution", scaffold.day).into());
+    }
+
+    let root = workspace_root();
+    let directory = root.join(scaffold.directory());
+
+    if directory.exists() {
+        return Err(format!("{} already exists", directory.display()).into());
+    }
+
+    let registrations = REGISTRATIONS
+        .into_iter()
+        .map(|(file, register)| {
+            let path = root.join(file);
+            let contents = fs::read_to_string(&path)
+                .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+
+            Ok((path, register(&scaffold, &contents)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    write_templates(&scaffold, &directory)?;
+
+    for (path, contents) in registrations {
+        fs::write(path, contents)?;
+    }
+
+    // The templates can't know how long the type name is, so rustfmt lays them out.
+    format_crate(&directory.join("Cargo.toml"))?;
+
+    println!(
+        "Created {} and registered it with the runner. Paste the example input in its \
+         fixtures/example.txt, put your input in its input.txt, then solve it in src/lib.rs.",
+        directory.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaffold() -> Scaffold {
+        Scaffold::new(11, "monkey_in_the_middle").unwrap()
+    }
+
+    #[test]
+    fn names_the_crate_after_the_puzzle() {
+        assert_eq!(scaffold().directory(), "11_monkey_in_the_middle");
+        assert_eq!(scaffold().type_name(), "MonkeyInTheMiddle");
+        assert_eq!(
+            scaffold().render("use {{name}}::{{type}};\nconst DAY: u8 = {{day}};"),
+            "use monkey_in_the_middle::MonkeyInTheMiddle;\nconst DAY: u8 = 11;"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_days_and_names() {
+        assert!(Scaffold::new(26, "too_late").is_err());
+        assert!(Scaffold::new(0, "too_early").is_err());
+
+        for name in [
+            "MonkeyInTheMiddle",
+            "monkey-in-the-middle",
+            "monkey__",
+            "11_monkeys",
+            "",
+        ] {
+            assert!(Scaffold::new(11, name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn leaves_no_placeholder_in_the_templates() {
+        for (file, template) in TEMPLATES {
+            assert!(!scaffold().render(template).contains("{{"), "{}", file);
+        }
+    }
+
+    #[test]
+    fn lays_out_the_new_day_like_the_others() {
+        let directory = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+
+        write_templates(&scaffold(), &directory).unwrap();
+
+        for file in [
+            "Cargo.toml",
+            "src/lib.rs",
+            "src/main.rs",
+            "fixtures/example.txt",
+        ] {
+            assert!(directory.join(file).is_file(), "{}", file);
+        }
+
+        assert_eq!(
+            fs::read_to_string(directory.join(".gitignore")).unwrap(),
+            fs::read_to_string(workspace_root().join("04_camp_cleanup/.gitignore")).unwrap()
+        );
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn registers_the_crate_after_the_last_day() {
+        let workspace = "[workspace]\nmembers = [\n    \"aoc\",\n    \"10_cathode_ray_tube\",\n]\n\n[profile.fuzz]\n";
+
+        assert_eq!(
+            scaffold().register_member(workspace).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"10_cathode_ray_tube\",\n    \"11_monkey_in_the_middle\",\n]\n\n[profile.fuzz]\n"
+        );
+
+        let runner = "[dependencies]\nrope_bridge = { path = \"../09_rope_bridge\" }\n";
+
+        assert_eq!(
+            scaffold().register_dependency(runner).unwrap(),
+            format!(
+                "{}monkey_in_the_middle = {{ path = \"../11_monkey_in_the_middle\" }}\n",
+                runner
+            )
+        );
+
+        let days = "pub static DAYS: &[&dyn Day] = &days![\n    rope_bridge::RopeBridge,\n];\n";
+
+        assert_eq!(
+            scaffold().register_day(days).unwrap(),
+            "pub static DAYS: &[&dyn Day] = &days![\n    rope_bridge::RopeBridge,\n    monkey_in_the_middle::MonkeyInTheMiddle,\n];\n"
+        );
+    }
+
+    #[test]
+    fn registers_with_the_actual_workspace_files() {
+        let root = workspace_root();
+
+        for (file, register) in REGISTRATIONS {
+            let contents = fs::read_to_string(root.join(file)).unwrap();
+
+            assert!(register(&scaffold(), &contents).is_ok(), "{}", file);
+        }
+    }
+}
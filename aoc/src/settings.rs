@@ -0,0 +1,148 @@
+use std::{any::Any, path::Path};
+
+use aoc_common::{
+    input::workspace_root,
+    toml::{Document, Table, Value},
+    Result,
+};
+
+use crate::days::{day_table, Day, DAYS};
+
+/// A setting given on the command line, by its `aoc.toml` key.
+pub type SettingOverride = (String, Value);
+
+/// The puzzle constants of `aoc.toml`, with the command line flags on top of them. Without either,
+/// every day keeps its puzzle's own constants.
+#[derive(Debug, Default)]
+pub struct Settings {
+    document: Document,
+    overrides: Vec<SettingOverride>,
+}
+
+impl Settings {
+    /// Reads `path`, or `aoc.toml` at the workspace root when there is one.
+    pub fn load(path: Option<&Path>, overrides: Vec<SettingOverride>) -> Result<Self> {
+        let default_path = workspace_root().join("aoc.toml");
+
+        let document = match path {
+            Some(path) => Document::read(path)?,
+            None if default_path.exists() => Document::read(default_path)?,
+            None => Document::default(),
+        };
+
+        check_tables(&document)?;
+
+        Ok(Self {
+            document,
+            overrides,
+        })
+    }
+
+    /// The settings of `day`, from its table (e.g. `[day07]`) and the flags it has a setting for.
+    pub fn table(&self, day: &dyn Day) -> Table {
+        let mut table = self
+            .document
+            .table(&day_table(day.number()))
+            .cloned()
+            .unwrap_or_default();
+
+        for (key, value) in self.overrides.iter() {
+            if day.setting_keys().contains(&key.as_str()) {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+
+        table
+    }
+
+    pub fn configure(&self, day: &dyn Day) -> Result<Box<dyn Any + Send + Sync>> {
+        day.configure(&self.table(day)).map_err(|error| {
+            format!("Invalid [{}] settings: {}", day_table(day.number()), error).into()
+        })
+    }
+
+    /// Makes sure every flag is a setting of one of `days`, instead of silently ignoring it.
+    pub fn check_overrides(&self, days: &[&dyn Day]) -> Result<()> {
+        for (key, _) in self.overrides.iter() {
+            if !days
+                .iter()
+                .any(|day| day.setting_keys().contains(&key.as_str()))
+            {
+                return Err(format!(
+                    "--{} is not a setting of the days being solved",
+                    key.replace('_', "-")
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Makes sure every table is a day's, so that a misspelled one (e.g. `[day7]`) isn't silently
+/// ignored along with its settings.
+fn check_tables(document: &Document) -> Result<()> {
+    for name in document.tables.keys() {
+        if !DAYS.iter().any(|day| *name == day_table(day.number())) {
+            return Err(match name.as_str() {
+                "" => "Settings should be in a day's table, like [day07]".into(),
+                name => format!(
+                    "[{}] is not a day's table, which are named like [day07]",
+                    name
+                )
+                .into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::toml;
+
+    use crate::days::find_day;
+
+    use super::*;
+
+    #[test]
+    fn puts_flags_on_top_of_the_day_tables() {
+        let settings = Settings {
+            document: toml::parse("[day07]\ndisk_size = 1\nsize_limit = 2\n").unwrap(),
+            overrides: vec![("size_limit".to_string(), Value::Integer(3))],
+        };
+
+        let table = settings.table(find_day(7).unwrap());
+
+        assert_eq!(table.get("disk_size"), Some(&Value::Integer(1)));
+        assert_eq!(table.get("size_limit"), Some(&Value::Integer(3)));
+        assert!(settings.table(find_day(6).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn rejects_settings_the_days_do_not_have() {
+        let settings = Settings {
+            document: toml::parse("[day06]\nknots = 3\n").unwrap(),
+            overrides: vec![("size_limit".to_string(), Value::Integer(3))],
+        };
+
+        assert!(settings.configure(find_day(6).unwrap()).is_err());
+        assert!(settings.check_overrides(&[find_day(6).unwrap()]).is_err());
+        assert!(settings.check_overrides(&[find_day(7).unwrap()]).is_ok());
+    }
+
+    #[test]
+    fn rejects_tables_of_no_day() {
+        assert!(check_tables(&toml::parse("[day07]\nsize_limit = 1\n").unwrap()).is_ok());
+
+        for document in [
+            "[day7]\nsize_limit = 1\n",
+            "[day42]\nsize_limit = 1\n",
+            "size_limit = 1\n",
+        ] {
+            assert!(check_tables(&toml::parse(document).unwrap()).is_err());
+        }
+    }
+}
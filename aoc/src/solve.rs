@@ -0,0 +1,182 @@
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use aoc_common::{
+    answer::{Answer, Part},
+    cache::{CacheStatus, InputCache},
+    Result,
+};
+
+use crate::{
+    days::{find_cached_day, find_day, find_streaming_day, CachedDay, Day, StreamingDay, DAYS},
+    memory::measure,
+    output::PartResult,
+    pool,
+    settings::Settings,
+    PARTS,
+};
+
+pub struct DayRequest<'a> {
+    pub day: u8,
+    pub parts: Vec<Part>,
+    pub input: Option<&'a str>,
+    pub stream: bool,
+    pub cache: bool,
+}
+
+struct Job<'a> {
+    day: &'static dyn Day,
+    streaming_day: Option<&'static dyn StreamingDay>,
+    cached_day: Option<&'static dyn CachedDay>,
+    parts: &'a [Part],
+    input: Option<&'a str>,
+    config: Box<dyn Any + Send + Sync>,
+}
+
+type Loaded = Arc<Result<Box<dyn Any + Send + Sync>, String>>;
+
+impl Job<'_> {
+    /// Parses the input (or reads it from `cache`), or goes through it once when streaming.
+    fn load(
+        &self,
+        cache: &InputCache,
+    ) -> Result<(Box<dyn Any + Send + Sync>, Option<CacheStatus>)> {
+        let input_source = self.day.input_source(self.input);
+
+        match (self.streaming_day, self.cached_day) {
+            (Some(streaming_day), _) => Ok((
+                streaming_day.summarize(&mut input_source.open()?, self.config.as_ref())?,
+                None,
+            )),
+            (None, Some(cached_day)) => {
+                let (parsed_input, status) = cached_day.parse(cache, &input_source.read()?)?;
+
+                Ok((parsed_input, Some(status)))
+            }
+            (None, None) => Ok((self.day.parse(&input_source.read()?)?, None)),
+        }
+    }
+
+    fn solve(&self, loaded: &Loaded, part: Part) -> Result<Answer> {
+        let loaded = match loaded.as_ref() {
+            Ok(loaded) => loaded.as_ref(),
+            Err(error) => return Err(error.clone().into()),
+        };
+
+        match self.streaming_day {
+            Some(streaming_day) => streaming_day.solve(loaded, self.config.as_ref(), part),
+            None => self.day.solve(loaded, self.config.as_ref(), part),
+        }
+    }
+}
+
+/// Solves the requested days with their `settings` on `thread_count` threads. Once a day's input
+/// is parsed, its parts are solved side by side too. Results come back ordered by day and part,
+/// and a day or part that panics fails only its own results.
+pub fn solve_days(
+    requests: &[DayRequest],
+    settings: &Settings,
+    thread_count: usize,
+) -> Result<Vec<PartResult>> {
+    let jobs = requests
+        .iter()
+        .map(|request| {
+            let day = find_day(request.day)?;
+
+            Ok(Job {
+                day,
+                streaming_day: match request.stream {
+                    true => Some(find_streaming_day(request.day)?),
+                    false => None,
+                },
+                cached_day: match request.cache {
+                    true => Some(find_cached_day(request.day)?),
+                    false => None,
+                },
+                parts: &request.parts,
+                input: request.input,
+                config: settings.configure(day)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    settings.check_overrides(&jobs.iter().map(|job| job.day).collect::<Vec<_>>())?;
+
+    let cache = InputCache::locate();
+    let results = Mutex::new(Vec::new());
+
+    pool::run(thread_count, |queue| {
+        for job in jobs.iter() {
+            let results = &results;
+            let cache = &cache;
+
+            queue.spawn(move |queue| {
+                let start = Instant::now();
+
+                let (loaded, load_memory) = measure(|| pool::catching_panics(|| job.load(cache)));
+
+                let parse_elapsed = start.elapsed();
+                let (loaded, cache_status) = match loaded {
+                    Ok((loaded, cache_status)) => (Ok(loaded), cache_status),
+                    Err(error) => (Err(error.to_string()), None),
+                };
+                let loaded: Loaded = Arc::new(loaded);
+
+                for &part in job.parts {
+                    let loaded = Arc::clone(&loaded);
+
+                    queue.spawn(move |_| {
+                        let start = Instant::now();
+
+                        let (answer, solve_memory) =
+                            measure(|| pool::catching_panics(|| job.solve(&loaded, part)));
+
+                        let result = PartResult {
+                            day: job.day.number(),
+                            part,
+                            answer,
+                            parse_elapsed,
+                            cache_status,
+                            elapsed: start.elapsed(),
+                            peak_memory: load_memory
+                                .peak
+                                .max(load_memory.retained + solve_memory.peak),
+                        };
+
+                        results.lock().unwrap().push(result);
+                    });
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+
+    results.sort_by_key(|result| (result.day, result.part));
+
+    Ok(results)
+}
+
+/// Solves every day, going through the parsed input cache for the days which have one when
+/// `cache` is set.
+pub fn solve_all_days(
+    settings: &Settings,
+    thread_count: usize,
+    cache: bool,
+) -> Result<Vec<PartResult>> {
+    let requests = DAYS
+        .iter()
+        .map(|day| DayRequest {
+            day: day.number(),
+            parts: PARTS.to_vec(),
+            input: None,
+            stream: false,
+            cache: cache && find_cached_day(day.number()).is_ok(),
+        })
+        .collect::<Vec<_>>();
+
+    solve_days(&requests, settings, thread_count)
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::Path};
+
+use aoc_common::{
+    answer::{Answer, Part},
+    toml::{Document, Value},
+    Result,
+};
+
+use crate::{days::day_table, output::PartResult};
+
+pub fn answer_key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+fn answer_to_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(value) => Value::Integer(*value),
+        Answer::Text(value) => Value::String(value.clone()),
+    }
+}
+
+fn value_lines(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => text.lines().map(String::from).collect(),
+        value => vec![value.to_string()],
+    }
+}
+
+fn diff(expected: &Value, actual: &Value) -> Vec<String> {
+    let expected_lines = value_lines(expected);
+    let actual_lines = value_lines(actual);
+
+    (0..expected_lines.len().max(actual_lines.len()))
+        .flat_map(
+            |index| match (expected_lines.get(index), actual_lines.get(index)) {
+                (Some(expected), Some(actual)) if expected == actual => {
+                    vec![format!("  {}", expected)]
+                }
+                (expected, actual) => expected
+                    .map(|expected| format!("- {}", expected))
+                    .into_iter()
+                    .chain(actual.map(|actual| format!("+ {}", actual)))
+                    .collect(),
+            },
+        )
+        .collect()
+}
+
+enum Verdict {
+    Correct,
+    Unknown,
+    Mismatch(Vec<String>),
+    Failed(String),
+}
+
+fn judge(expected_answers: &Document, result: &PartResult) -> Verdict {
+    let expected = expected_answers.get(&day_table(result.day), &answer_key(result.part));
+
+    match (&result.answer, expected) {
+        (Err(error), _) => Verdict::Failed(error.to_string()),
+        (Ok(_), None) => Verdict::Unknown,
+        (Ok(answer), Some(expected)) => {
+            let actual = answer_to_value(answer);
+
+            if &actual == expected {
+                Verdict::Correct
+            } else {
+                Verdict::Mismatch(diff(expected, &actual))
+            }
+        }
+    }
+}
+
+pub fn verify(results: &[PartResult], answers_path: &Path) -> Result<()> {
+    let expected_answers = Document::read(answers_path)?;
+
+    let mut problems = 0;
+
+    println!("{:>3}  {:>4}  Result", "Day", "Part");
+
+    for result in results {
+        let (label, details) = match judge(&expected_answers, result) {
+            Verdict::Correct => ("ok", vec![]),
+            Verdict::Unknown => ("no expected answer", vec![]),
+            Verdict::Mismatch(diff) => ("MISMATCH", diff),
+            Verdict::Failed(error) => ("FAILED", error.lines().map(String::from).collect()),
+        };
+
+        if !details.is_empty() {
+            problems += 1;
+        }
+
+        println!("{:>3}  {:>4}  {}", result.day, result.part, label);
+
+        for line in details {
+            println!("{:>3}  {:>4}    {}", "", "", line);
+        }
+    }
+
+    match problems {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} answers did not match {}",
+            problems,
+            answers_path.display()
+        )
+        .into()),
+    }
+}
+
+pub fn record(results: &[PartResult], answers_path: &Path) -> Result<()> {
+    let mut answers = match answers_path.exists() {
+        true => Document::read(answers_path)?,
+        false => Document::default(),
+    };
+
+    for result in results {
+        let answer = result.answer.as_ref().map_err(|error| {
+            format!(
+                "Day {} part {} could not be solved: {}",
+                result.day, result.part, error
+            )
+        })?;
+
+        answers.insert(
+            &day_table(result.day),
+            &answer_key(result.part),
+            answer_to_value(answer),
+        );
+    }
+
+    fs::write(answers_path, answers.to_string())
+        .map_err(|error| format!("Could not write {}: {}", answers_path.display(), error))?;
+
+    println!(
+        "Recorded {} answers in {}",
+        results.len(),
+        answers_path.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,544 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use aoc_common::{
+    input::{workspace_root, InputSource},
+    Result,
+};
+
+use crate::{
+    cli::WatchOptions,
+    days::{find_day, Day},
+    output::Format,
+    settings::Settings,
+    solve::{solve_days, DayRequest},
+    PARTS,
+};
+
+const POLLING_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What changed, with `Source` winning when both did since it needs a rebuild anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Change {
+    Input,
+    Source,
+}
+
+trait Watcher {
+    /// Blocks until the input or one of the source files changes.
+    fn wait(&mut self) -> Result<Change>;
+}
+
+/// Checks modification times every `POLLING_INTERVAL`, where inotify isn't available.
+struct PollingWatcher {
+    input: PathBuf,
+    source_directory: PathBuf,
+    input_stamp: Option<SystemTime>,
+    source_stamp: Option<SystemTime>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The latest modification in a directory, the directory itself included so that creating or
+/// deleting files counts too.
+fn latest_modification(directory: &Path) -> Option<SystemTime> {
+    let entries = fs::read_dir(directory).into_iter().flatten().flatten();
+
+    entries
+        .filter_map(|entry| modified(&entry.path()))
+        .chain(modified(directory))
+        .max()
+}
+
+impl PollingWatcher {
+    fn new(input: &Path, source_directory: &Path) -> Self {
+        Self {
+            input: input.to_path_buf(),
+            source_directory: source_directory.to_path_buf(),
+            input_stamp: modified(input),
+            source_stamp: latest_modification(source_directory),
+        }
+    }
+
+    fn poll(&mut self) -> Option<Change> {
+        let input_stamp = modified(&self.input);
+        let source_stamp = latest_modification(&self.source_directory);
+
+        let input_changed = input_stamp != self.input_stamp;
+        let source_changed = source_stamp != self.source_stamp;
+
+        self.input_stamp = input_stamp;
+        self.source_stamp = source_stamp;
+
+        match (input_changed, source_changed) {
+            (_, true) => Some(Change::Source),
+            (true, false) => Some(Change::Input),
+            (false, false) => None,
+        }
+    }
+}
+
+impl Watcher for PollingWatcher {
+    fn wait(&mut self) -> Result<Change> {
+        loop {
+            thread::sleep(POLLING_INTERVAL);
+
+            if let Some(change) = self.poll() {
+                return Ok(change);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::{
+        ffi::{CString, OsStr, OsString},
+        fs::File,
+        io::{self, Read},
+        os::{
+            fd::{AsRawFd, FromRawFd},
+            raw::{c_char, c_int, c_short, c_ulong},
+            unix::ffi::OsStrExt,
+        },
+        path::Path,
+    };
+
+    use aoc_common::Result;
+
+    use super::{Change, Watcher};
+
+    const IN_CLOEXEC: c_int = 0o2000000;
+
+    const IN_CLOSE_WRITE: u32 = 0x0000_0008;
+    const IN_MOVED_TO: u32 = 0x0000_0080;
+    const IN_CREATE: u32 = 0x0000_0100;
+    const IN_DELETE: u32 = 0x0000_0200;
+
+    const POLLIN: c_short = 0x0001;
+
+    /// Editors often save through several events (e.g. writing a temporary file and renaming it),
+    /// so events are gathered until the watched directories stay quiet this long.
+    const SETTLE_MILLISECONDS: c_int = 50;
+
+    #[repr(C)]
+    struct PollFd {
+        fd: c_int,
+        events: c_short,
+        revents: c_short,
+    }
+
+    extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+        fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Event {
+        pub watch: c_int,
+        pub name: OsString,
+    }
+
+    /// Splits what a read on an inotify descriptor returned into events, each being a
+    /// `struct inotify_event` header followed by a NUL-padded name.
+    pub fn parse_events(buffer: &[u8]) -> Vec<Event> {
+        const HEADER_LENGTH: usize = 16;
+
+        let mut events = vec![];
+        let mut offset = 0;
+
+        while offset + HEADER_LENGTH <= buffer.len() {
+            let field = |index: usize| {
+                let start = offset + index * 4;
+
+                <[u8; 4]>::try_from(&buffer[start..start + 4]).unwrap()
+            };
+
+            let watch = c_int::from_ne_bytes(field(0));
+            let name_length = u32::from_ne_bytes(field(3)) as usize;
+
+            let name_start = offset + HEADER_LENGTH;
+            let name_end = (name_start + name_length).min(buffer.len());
+            let name = &buffer[name_start..name_end];
+            let name = &name[..name
+                .iter()
+                .position(|&byte| byte == 0)
+                .unwrap_or(name.len())];
+
+            events.push(Event {
+                watch,
+                name: OsStr::from_bytes(name).to_os_string(),
+            });
+
+            offset = name_end;
+        }
+
+        events
+    }
+
+    pub struct Inotify {
+        file: File,
+    }
+
+    impl Inotify {
+        pub fn new() -> io::Result<Self> {
+            let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self {
+                file: unsafe { File::from_raw_fd(fd) },
+            })
+        }
+
+        /// Watches files being written, created, moved in or deleted inside `directory`.
+        pub fn watch_directory(&self, directory: &Path) -> io::Result<c_int> {
+            let path = CString::new(directory.as_os_str().as_bytes())
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+            let watch = unsafe {
+                inotify_add_watch(
+                    self.file.as_raw_fd(),
+                    path.as_ptr(),
+                    IN_CLOSE_WRITE | IN_MOVED_TO | IN_CREATE | IN_DELETE,
+                )
+            };
+
+            match watch {
+                watch if watch < 0 => Err(io::Error::last_os_error()),
+                watch => Ok(watch),
+            }
+        }
+
+        /// Whether events arrived within `timeout_milliseconds` (forever when negative).
+        pub fn wait(&self, timeout_milliseconds: c_int) -> io::Result<bool> {
+            let mut poll_fd = PollFd {
+                fd: self.file.as_raw_fd(),
+                events: POLLIN,
+                revents: 0,
+            };
+
+            loop {
+                match unsafe { poll(&mut poll_fd, 1, timeout_milliseconds) } {
+                    ready if ready >= 0 => return Ok(ready > 0),
+                    _ => {
+                        let error = io::Error::last_os_error();
+
+                        if error.kind() != io::ErrorKind::Interrupted {
+                            return Err(error);
+                        }
+                    }
+                }
+            }
+        }
+
+        pub fn read_events(&mut self) -> io::Result<Vec<Event>> {
+            let mut buffer = [0; 4096];
+
+            let read = self.file.read(&mut buffer)?;
+
+            Ok(parse_events(&buffer[..read]))
+        }
+    }
+
+    pub struct InotifyWatcher {
+        inotify: Inotify,
+        input_watch: c_int,
+        input_name: OsString,
+        source_watch: c_int,
+    }
+
+    impl InotifyWatcher {
+        /// Watches the directories rather than the files themselves, as saving through a rename
+        /// replaces the watched file.
+        pub fn new(input: &Path, source_directory: &Path) -> io::Result<Self> {
+            let inotify = Inotify::new()?;
+
+            let input_directory = match input.parent() {
+                Some(directory) if !directory.as_os_str().is_empty() => directory,
+                _ => Path::new("."),
+            };
+
+            Ok(Self {
+                input_watch: inotify.watch_directory(input_directory)?,
+                input_name: input.file_name().unwrap_or_default().to_os_string(),
+                source_watch: inotify.watch_directory(source_directory)?,
+                inotify,
+            })
+        }
+
+        fn change(&self, events: &[Event]) -> Option<Change> {
+            events
+                .iter()
+                .filter_map(|event| {
+                    if event.watch == self.source_watch {
+                        Some(Change::Source)
+                    } else if event.watch == self.input_watch && event.name == self.input_name {
+                        Some(Change::Input)
+                    } else {
+                        None
+                    }
+                })
+                .max()
+        }
+    }
+
+    impl Watcher for InotifyWatcher {
+        fn wait(&mut self) -> Result<Change> {
+            loop {
+                self.inotify.wait(-1)?;
+
+                let events = self.inotify.read_events()?;
+                let mut change = self.change(&events);
+
+                if change.is_none() {
+                    continue;
+                }
+
+                while self.inotify.wait(SETTLE_MILLISECONDS)? {
+                    let events = self.inotify.read_events()?;
+                    change = change.max(self.change(&events));
+                }
+
+                return Ok(change.unwrap());
+            }
+        }
+    }
+}
+
+fn watcher(input: &Path, source_directory: &Path, polling: bool) -> Box<dyn Watcher> {
+    #[cfg(target_os = "linux")]
+    if !polling {
+        match inotify::InotifyWatcher::new(input, source_directory) {
+            Ok(watcher) => return Box::new(watcher),
+            Err(error) => eprintln!("Could not use inotify ({}), polling instead", error),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = polling;
+
+    Box::new(PollingWatcher::new(input, source_directory))
+}
+
+fn show(day: &dyn Day, input: &str, source_directory: &Path, run: usize, options: &WatchOptions) {
+    let request = DayRequest {
+        day: day.number(),
+        parts: PARTS.to_vec(),
+        input: Some(input),
+        stream: false,
+        // Parser edits don't bump the parser version, so a watched day is always parsed again.
+        cache: false,
+    };
+
+    let threads = options.threads;
+
+    let start = Instant::now();
+    // Settings are read again on every run, so aoc.toml edits apply from the next save.
+    let results = Settings::load(options.config.as_deref(), options.overrides.clone())
+        .and_then(|settings| solve_days(&[request], &settings, threads));
+    let wall_time = start.elapsed();
+
+    // Clears the terminal so the answers stay in place.
+    print!("\x1b[2J\x1b[H");
+
+    println!(
+        "Watching {} and {} (run {}, Ctrl+C to stop)\n",
+        input,
+        source_directory.display(),
+        run
+    );
+
+    match results {
+        Ok(results) => {
+            Format::Text.print_header();
+
+            for result in results.iter() {
+                Format::Text.print(result);
+            }
+
+            Format::Text.print_footer(&results, wall_time, threads);
+        }
+        Err(error) => println!("{}", error),
+    }
+
+    let _ = io::stdout().flush();
+}
+
+/// Rebuilds the runner with the profile it was built with and replaces the running process with
+/// the new build. Only returns when that fails.
+fn rebuild_and_restart() -> Result<()> {
+    let executable = env::current_exe()?;
+
+    let profile = executable
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|profile| profile.to_str())
+        .unwrap_or("debug");
+
+    let mut build = process::Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+
+    build
+        .args(["build", "-p", "aoc"])
+        .current_dir(workspace_root());
+
+    if profile != "debug" {
+        build.args(["--profile", profile]);
+    }
+
+    if !build.status()?.success() {
+        return Err("The build failed. Fix it and save again.".into());
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        let error = process::Command::new(executable)
+            .args(env::args_os().skip(1))
+            .exec();
+
+        Err(format!("Could not restart after rebuilding: {}", error).into())
+    }
+
+    #[cfg(not(unix))]
+    Err("Rebuilt. Restart aoc watch to use the new build.".into())
+}
+
+pub fn watch(options: WatchOptions) -> Result<()> {
+    let day = find_day(options.day)?;
+
+    let input = match day.input_source(options.input.as_deref()) {
+        InputSource::File(path) => path,
+        InputSource::Stdin => return Err("aoc watch needs an input file to watch".into()),
+    };
+
+    // The day is solved through the same input argument as `run`, which is a string.
+    let input = input.into_os_string().into_string().map_err(|path| {
+        format!(
+            "aoc watch needs an input path in UTF-8, not {}",
+            Path::new(&path).display()
+        )
+    })?;
+
+    let source_directory = workspace_root().join(day.directory()).join("src");
+
+    let mut watcher = watcher(Path::new(&input), &source_directory, options.poll);
+
+    let mut run = 1;
+
+    show(day, &input, &source_directory, run, &options);
+
+    loop {
+        match watcher.wait()? {
+            Change::Input => {}
+            Change::Source => {
+                println!("\nSources changed, rebuilding...");
+
+                if let Err(error) = rebuild_and_restart() {
+                    eprintln!("{}", error);
+
+                    continue;
+                }
+            }
+        }
+
+        run += 1;
+
+        show(day, &input, &source_directory, run, &options);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_directory(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc-watch-{}-{}", name, process::id()));
+
+        fs::create_dir_all(directory.join("src")).unwrap();
+
+        directory
+    }
+
+    #[test]
+    fn polls_for_changed_modification_times() {
+        let directory = temporary_directory("poll");
+        let input = directory.join("input.txt");
+
+        fs::write(&input, "1").unwrap();
+
+        let mut watcher = PollingWatcher::new(&input, &directory.join("src"));
+
+        assert_eq!(watcher.poll(), None);
+
+        watcher.input_stamp = Some(SystemTime::UNIX_EPOCH);
+        assert_eq!(watcher.poll(), Some(Change::Input));
+
+        fs::write(directory.join("src").join("lib.rs"), "").unwrap();
+        watcher.source_stamp = Some(SystemTime::UNIX_EPOCH);
+        assert_eq!(watcher.poll(), Some(Change::Source));
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_inotify_events() {
+        let mut buffer = vec![];
+
+        for (watch, name) in [(1i32, &b"input.txt\0\0\0"[..]), (2, &b""[..])] {
+            buffer.extend(watch.to_ne_bytes());
+            buffer.extend(8u32.to_ne_bytes());
+            buffer.extend(0u32.to_ne_bytes());
+            buffer.extend((name.len() as u32).to_ne_bytes());
+            buffer.extend(name);
+        }
+
+        let events = inotify::parse_events(&buffer);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            (events[0].watch, events[0].name.to_str()),
+            (1, Some("input.txt"))
+        );
+        assert_eq!((events[1].watch, events[1].name.to_str()), (2, Some("")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn notices_saved_inputs_and_sources() {
+        let directory = temporary_directory("inotify");
+        let input = directory.join("input.txt");
+        let source_directory = directory.join("src");
+
+        let mut watcher = inotify::InotifyWatcher::new(&input, &source_directory).unwrap();
+
+        fs::write(directory.join("other.txt"), "ignored").unwrap();
+        fs::write(&input, "1").unwrap();
+        assert_eq!(watcher.wait().unwrap(), Change::Input);
+
+        // Saving through a rename, like many editors do.
+        fs::write(directory.join("input.txt.swp"), "2").unwrap();
+        fs::rename(directory.join("input.txt.swp"), &input).unwrap();
+        assert_eq!(watcher.wait().unwrap(), Change::Input);
+
+        fs::write(&input, "3").unwrap();
+        fs::write(source_directory.join("lib.rs"), "").unwrap();
+        assert_eq!(watcher.wait().unwrap(), Change::Source);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
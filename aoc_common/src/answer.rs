@@ -0,0 +1,82 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => f.pad("1"),
+            Self::Two => f.pad("2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Self::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+/// Kept as text past `i128::MAX`, which is all the runner does with answers anyway.
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Self::Text(value.to_string()), Self::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+pub fn report(part: Part, description: impl fmt::Display) {
+    println!("Part {}: {}", part, description);
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(part_str: &str) -> Result<Self, Self::Err> {
+        match part_str {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => Err(format!("A part should be 1 or 2. Got: {}", part_str).into()),
+        }
+    }
+}
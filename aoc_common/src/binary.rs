@@ -0,0 +1,370 @@
+use std::{collections::HashMap, hash::Hash, path::PathBuf};
+
+use crate::{grid::Grid, Result};
+
+/// A compact binary encoding: integers are LEB128 varints (zigzagged when signed), and strings
+/// and collections are prefixed with their length.
+pub trait Binary: Sized {
+    fn encode(&self, encoder: &mut Encoder);
+
+    fn decode(decoder: &mut Decoder) -> Result<Self>;
+}
+
+#[derive(Debug, Default)]
+pub struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn write_u8(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+
+        self.bytes.push(value as u8);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .position
+            .checked_add(length)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or("The encoded data ends too early")?;
+
+        self.position += length;
+
+        Ok(bytes)
+    }
+
+    pub fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err("An encoded integer is longer than 64 bits".into())
+    }
+
+    /// A length prefix, which can't be larger than what is left to read.
+    pub fn read_length(&mut self) -> Result<usize> {
+        let length = self.read_varint()?;
+
+        match usize::try_from(length) {
+            Ok(length) if length <= self.bytes.len() - self.position => Ok(length),
+            _ => Err(format!("An encoded length of {} is larger than the data", length).into()),
+        }
+    }
+
+    /// Makes sure every byte was decoded.
+    pub fn finish(&self) -> Result<()> {
+        match self.position == self.bytes.len() {
+            true => Ok(()),
+            false => Err(format!(
+                "{} encoded bytes were left over",
+                self.bytes.len() - self.position
+            )
+            .into()),
+        }
+    }
+}
+
+pub fn to_bytes<T: Binary>(value: &T) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+
+    value.encode(&mut encoder);
+
+    encoder.into_bytes()
+}
+
+pub fn from_bytes<T: Binary>(bytes: &[u8]) -> Result<T> {
+    let mut decoder = Decoder::new(bytes);
+    let value = T::decode(&mut decoder)?;
+
+    decoder.finish()?;
+
+    Ok(value)
+}
+
+impl Binary for u8 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_u8(*self);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        decoder.read_u8()
+    }
+}
+
+macro_rules! impl_binary_for_unsigned {
+    ($($unsigned:ty),*) => {
+        $(
+            impl Binary for $unsigned {
+                fn encode(&self, encoder: &mut Encoder) {
+                    encoder.write_varint(*self as u64);
+                }
+
+                fn decode(decoder: &mut Decoder) -> Result<Self> {
+                    let value = decoder.read_varint()?;
+
+                    <$unsigned>::try_from(value).map_err(|_| {
+                        format!("{} doesn't fit in a {}", value, stringify!($unsigned)).into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_for_unsigned!(u16, u32, u64, usize);
+
+macro_rules! impl_binary_for_signed {
+    ($($signed:ty),*) => {
+        $(
+            impl Binary for $signed {
+                fn encode(&self, encoder: &mut Encoder) {
+                    let value = *self as i64;
+
+                    encoder.write_varint(((value << 1) ^ (value >> 63)) as u64);
+                }
+
+                fn decode(decoder: &mut Decoder) -> Result<Self> {
+                    let zigzag = decoder.read_varint()?;
+                    let value = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+
+                    <$signed>::try_from(value).map_err(|_| {
+                        format!("{} doesn't fit in a {}", value, stringify!($signed)).into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_for_signed!(i8, i16, i32, i64, isize);
+
+impl Binary for bool {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_u8(u8::from(*self));
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        match decoder.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(format!("{} is not an encoded boolean", byte).into()),
+        }
+    }
+}
+
+impl Binary for char {
+    fn encode(&self, encoder: &mut Encoder) {
+        (*self as u32).encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        let code_point = u32::decode(decoder)?;
+
+        char::from_u32(code_point)
+            .ok_or_else(|| format!("{:#x} is not a character", code_point).into())
+    }
+}
+
+impl Binary for String {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.len().encode(encoder);
+        encoder.write_bytes(self.as_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        let length = decoder.read_length()?;
+        let bytes = decoder.read_bytes(length)?;
+
+        Ok(String::from_utf8(bytes.to_vec())?)
+    }
+}
+
+/// Paths are encoded as strings, so they should be valid UTF-8 (like the ones built while
+/// parsing).
+impl Binary for PathBuf {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.to_string_lossy().into_owned().encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok(PathBuf::from(String::decode(decoder)?))
+    }
+}
+
+impl<T: Binary> Binary for Option<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.is_some().encode(encoder);
+
+        if let Some(value) = self {
+            value.encode(encoder);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        match bool::decode(decoder)? {
+            true => Ok(Some(T::decode(decoder)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T: Binary> Binary for Vec<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.len().encode(encoder);
+
+        for item in self.iter() {
+            item.encode(encoder);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        // Every item takes at least a byte, which `read_length` checks is left, so a corrupted
+        // length can't allocate more than the data's size.
+        let length = decoder.read_length()?;
+
+        (0..length).map(|_| T::decode(decoder)).collect()
+    }
+}
+
+impl<K: Binary + Eq + Hash, V: Binary> Binary for HashMap<K, V> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.len().encode(encoder);
+
+        for (key, value) in self.iter() {
+            key.encode(encoder);
+            value.encode(encoder);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        let length = decoder.read_length()?;
+
+        (0..length)
+            .map(|_| Ok((K::decode(decoder)?, V::decode(decoder)?)))
+            .collect()
+    }
+}
+
+impl<A: Binary, B: Binary> Binary for (A, B) {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.0.encode(encoder);
+        self.1.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        Ok((A::decode(decoder)?, B::decode(decoder)?))
+    }
+}
+
+impl<T: Binary> Binary for Grid<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.width().encode(encoder);
+        self.height().encode(encoder);
+
+        for cell in self.cells() {
+            cell.encode(encoder);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self> {
+        let width = usize::decode(decoder)?;
+        let height = usize::decode(decoder)?;
+
+        let cell_count = width
+            .checked_mul(height)
+            .filter(|&cell_count| cell_count <= decoder.bytes.len() - decoder.position)
+            .ok_or_else(|| format!("A {}x{} grid is larger than the data", width, height))?;
+
+        let cells = (0..cell_count)
+            .map(|_| T::decode(decoder))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Grid::from_cells(width, height, cells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Binary + PartialEq + std::fmt::Debug>(value: T) {
+        assert_eq!(from_bytes::<T>(&to_bytes(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn round_trips_values() {
+        round_trip(0u32);
+        round_trip(u64::MAX);
+        round_trip(i32::MIN);
+        round_trip(-1i64);
+        round_trip('🦀');
+        round_trip("crates".to_string());
+        round_trip(vec![Some(1u8), None]);
+        round_trip(PathBuf::from("/a/b"));
+        round_trip(HashMap::from([
+            ("a".to_string(), vec!['x']),
+            ("b".to_string(), vec![]),
+        ]));
+        round_trip((true, -5i8));
+        round_trip(Grid::from_fn(3, 2, |(x, y)| (x * y) as u8));
+    }
+
+    #[test]
+    fn encodes_small_integers_in_a_byte() {
+        assert_eq!(to_bytes(&127u32), vec![0x7f]);
+        assert_eq!(to_bytes(&128u32), vec![0x80, 0x01]);
+        assert_eq!(to_bytes(&-1i32), vec![0x01]);
+        assert_eq!(to_bytes(&"ab".to_string()), vec![2, b'a', b'b']);
+    }
+
+    #[test]
+    fn rejects_corrupted_data() {
+        assert!(from_bytes::<u8>(&[]).is_err());
+        assert!(from_bytes::<u8>(&[1, 2]).is_err());
+        assert!(from_bytes::<u8>(&to_bytes(&300u32)).is_err());
+        assert!(from_bytes::<bool>(&[2]).is_err());
+        assert!(from_bytes::<String>(&[2, 0xff, 0xfe]).is_err());
+        assert!(from_bytes::<Vec<u64>>(&[0xff, 0xff, 0xff, 0xff, 0x0f]).is_err());
+        assert!(from_bytes::<u64>(&[0xff; 11]).is_err());
+        assert!(from_bytes::<Grid<u8>>(&[0xff, 0x01, 0xff, 0x01]).is_err());
+    }
+}
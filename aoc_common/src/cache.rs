@@ -0,0 +1,268 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::{
+    binary::{self, Binary, Decoder, Encoder},
+    input::workspace_root,
+    Result, Solution,
+};
+
+pub const CACHE_DIR_VARIABLE: &str = "AOC_CACHE_DIR";
+
+const MAGIC: &[u8; 4] = b"AOCP";
+
+/// Bumped whenever the layout of the cache files (not of a day's input) changes.
+const FORMAT_VERSION: u8 = 1;
+
+/// A solution whose parsed input can be cached in binary form, skipping the parser on inputs it
+/// has already seen.
+pub trait CachedSolution: Solution<Input: Binary> {
+    /// Bump it whenever the parser or the encoding of the parsed input changes, so that the
+    /// entries written by the previous version are ignored.
+    const PARSER_VERSION: u32;
+}
+
+/// 64-bit FNV-1a, which is stable across Rust versions and platforms, unlike `DefaultHasher`.
+pub fn content_hash(content: &[u8]) -> u64 {
+    content.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// What a cache entry is for: an input of a day, read by a version of its parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheKey {
+    pub day: u8,
+    pub parser_version: u32,
+    pub content_hash: u64,
+    pub content_length: usize,
+}
+
+impl CacheKey {
+    pub fn new(day: u8, parser_version: u32, content: &str) -> Self {
+        Self {
+            day,
+            parser_version,
+            content_hash: content_hash(content.as_bytes()),
+            content_length: content.len(),
+        }
+    }
+
+    fn day_prefix(&self) -> String {
+        format!("day{:02}-", self.day)
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{}v{}-{:016x}.bin",
+            self.day_prefix(),
+            self.parser_version,
+            self.content_hash
+        )
+    }
+
+    fn encode_header(&self, encoder: &mut Encoder) {
+        encoder.write_bytes(MAGIC);
+        encoder.write_u8(FORMAT_VERSION);
+        self.day.encode(encoder);
+        self.parser_version.encode(encoder);
+        self.content_hash.encode(encoder);
+        self.content_length.encode(encoder);
+    }
+
+    fn matches_header(&self, decoder: &mut Decoder) -> Result<bool> {
+        Ok(decoder.read_bytes(MAGIC.len())? == MAGIC
+            && decoder.read_u8()? == FORMAT_VERSION
+            && u8::decode(decoder)? == self.day
+            && u32::decode(decoder)? == self.parser_version
+            && u64::decode(decoder)? == self.content_hash
+            && usize::decode(decoder)? == self.content_length)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+/// A directory of parsed inputs, one file per day, input and parser version.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    directory: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// `$AOC_CACHE_DIR` when it is set, `target/aoc-cache` inside this repository otherwise.
+    pub fn locate() -> Self {
+        Self::new(
+            env::var_os(CACHE_DIR_VARIABLE)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| workspace_root().join("target").join("aoc-cache")),
+        )
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The value cached under `key`. Missing, stale and corrupted entries are all misses.
+    pub fn load<T: Binary>(&self, key: &CacheKey) -> Option<T> {
+        let bytes = fs::read(self.directory.join(key.file_name())).ok()?;
+        let mut decoder = Decoder::new(&bytes);
+
+        if !key.matches_header(&mut decoder).ok()? {
+            return None;
+        }
+
+        let value = T::decode(&mut decoder).ok()?;
+
+        decoder.finish().ok().map(|_| value)
+    }
+
+    /// Caches `value` under `key`, replacing the entries of older inputs or parsers of the same
+    /// day so that the cache doesn't grow with every edit of an input.
+    pub fn store<T: Binary>(&self, key: &CacheKey, value: &T) -> Result<()> {
+        let mut encoder = Encoder::default();
+
+        key.encode_header(&mut encoder);
+        value.encode(&mut encoder);
+
+        fs::create_dir_all(&self.directory).map_err(|error| self.write_error(error))?;
+
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+
+            if path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .is_some_and(|file_name| file_name.starts_with(&key.day_prefix()))
+            {
+                fs::remove_file(path).map_err(|error| self.write_error(error))?;
+            }
+        }
+
+        // Written aside first, so that concurrent runs never read a half-written entry.
+        let path = self.directory.join(key.file_name());
+        let partial_path = path.with_extension(format!("{}.partial", process::id()));
+
+        fs::write(&partial_path, encoder.into_bytes()).map_err(|error| self.write_error(error))?;
+        fs::rename(&partial_path, &path).map_err(|error| self.write_error(error))?;
+
+        Ok(())
+    }
+
+    /// Parses `input` with `S`'s parser, unless it was parsed (and cached) before. Failing to
+    /// write the cache doesn't fail the parse, it only means the next run parses again.
+    pub fn parse<S: CachedSolution>(&self, input: &str) -> Result<(S::Input, CacheStatus)> {
+        let key = CacheKey::new(S::DAY, S::PARSER_VERSION, input);
+
+        if let Some(parsed) = self.load(&key) {
+            return Ok((parsed, CacheStatus::Hit));
+        }
+
+        let parsed = S::parse(input)?;
+
+        let _ = self.store(&key, &parsed);
+
+        Ok((parsed, CacheStatus::Miss))
+    }
+
+    fn write_error(&self, error: io::Error) -> String {
+        format!(
+            "Could not write the input cache in {}: {}",
+            self.directory.display(),
+            error
+        )
+    }
+}
+
+/// Encodes `value` the way the cache would, then decodes it back.
+pub fn round_trip<T: Binary>(value: &T) -> Result<T> {
+    binary::from_bytes(&binary::to_bytes(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> InputCache {
+        let directory = env::temp_dir().join(format!("aoc-cache-{}-{}", name, process::id()));
+
+        let _ = fs::remove_dir_all(&directory);
+
+        InputCache::new(directory)
+    }
+
+    #[test]
+    fn hashes_contents_with_fnv_1a() {
+        assert_eq!(content_hash(b""), 0xcbf29ce484222325);
+        assert_eq!(content_hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(content_hash(b"1-2,3-4"), content_hash(b"1-2,3-5"));
+    }
+
+    #[test]
+    fn loads_what_was_stored_for_the_same_key() {
+        let cache = cache("hit");
+        let key = CacheKey::new(4, 1, "1-2,3-4\n");
+
+        assert_eq!(cache.load::<Vec<u32>>(&key), None);
+
+        cache.store(&key, &vec![1u32, 2, 3, 4]).unwrap();
+
+        assert_eq!(cache.load::<Vec<u32>>(&key), Some(vec![1, 2, 3, 4]));
+        assert_eq!(cache.load::<u32>(&key), None);
+
+        fs::remove_dir_all(cache.directory()).unwrap();
+    }
+
+    #[test]
+    fn misses_when_the_input_or_the_parser_changes() {
+        let cache = cache("miss");
+        let key = CacheKey::new(4, 1, "1-2,3-4\n");
+
+        cache.store(&key, &7u32).unwrap();
+
+        assert_eq!(cache.load::<u32>(&CacheKey::new(4, 1, "1-2,3-5\n")), None);
+        assert_eq!(cache.load::<u32>(&CacheKey::new(4, 2, "1-2,3-4\n")), None);
+        assert_eq!(cache.load::<u32>(&CacheKey::new(5, 1, "1-2,3-4\n")), None);
+
+        // A newer entry of the same day replaces the older one.
+        let newer_key = CacheKey::new(4, 2, "1-2,3-4\n");
+
+        cache.store(&newer_key, &8u32).unwrap();
+
+        assert_eq!(cache.load::<u32>(&key), None);
+        assert_eq!(cache.load::<u32>(&newer_key), Some(8));
+        assert_eq!(fs::read_dir(cache.directory()).unwrap().count(), 1);
+
+        fs::remove_dir_all(cache.directory()).unwrap();
+    }
+
+    #[test]
+    fn ignores_corrupted_entries() {
+        let cache = cache("corrupted");
+        let key = CacheKey::new(10, 1, "noop\n");
+
+        cache.store(&key, &vec![-1i32, 2]).unwrap();
+
+        let path = cache.directory().join(key.file_name());
+        let mut bytes = fs::read(&path).unwrap();
+
+        bytes.truncate(bytes.len() - 1);
+        fs::write(&path, bytes).unwrap();
+
+        assert_eq!(cache.load::<Vec<i32>>(&key), None);
+
+        fs::remove_dir_all(cache.directory()).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    toml::{Table, Value},
+    Error, Result,
+};
+
+/// The constants a puzzle is solved with, which a day's table in `aoc.toml` or command line flags
+/// can override.
+pub trait Config: Default + Send + Sync + 'static {
+    /// The settings as named in `aoc.toml`, which are `--kebab-case` flags on the command line.
+    const KEYS: &'static [&'static str];
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<()>;
+
+    /// Checks the settings which only make sense together, once all of them are set.
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// The defaults, with the settings of `table` on top of them.
+    fn from_table(table: &Table) -> Result<Self> {
+        let mut config = Self::default();
+
+        for (key, value) in table.iter() {
+            config.set(key, value)?;
+        }
+
+        config.validate()?;
+
+        Ok(config)
+    }
+}
+
+/// For puzzles without any constant worth changing.
+impl Config for () {
+    const KEYS: &'static [&'static str] = &[];
+
+    fn set(&mut self, key: &str, _: &Value) -> Result<()> {
+        Err(unknown_key(key, Self::KEYS))
+    }
+}
+
+pub fn unknown_key(key: &str, keys: &[&str]) -> Error {
+    match keys {
+        [] => format!("There is no setting called {}, this day has none", key).into(),
+        keys => format!(
+            "There is no setting called {}, only {}",
+            key,
+            keys.join(", ")
+        )
+        .into(),
+    }
+}
+
+/// Reads a setting that should be an integer no smaller than `minimum`.
+pub fn integer_at_least<T: TryFrom<i128>>(key: &str, value: &Value, minimum: i128) -> Result<T> {
+    match value {
+        Value::Integer(integer) if *integer >= minimum => {
+            T::try_from(*integer).map_err(|_| format!("{} = {} is too large", key, integer).into())
+        }
+        value => Err(format!(
+            "{} should be an integer of at least {}, not {}",
+            key, minimum, value
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Marker {
+        size: usize,
+    }
+
+    impl Config for Marker {
+        const KEYS: &'static [&'static str] = &["size"];
+
+        fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+            match key {
+                "size" => self.size = integer_at_least(key, value, 1)?,
+                _ => return Err(unknown_key(key, Self::KEYS)),
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn overrides_defaults_with_table_settings() {
+        let table = Table::from([("size".to_string(), Value::Integer(14))]);
+
+        assert_eq!(Marker::from_table(&table).unwrap(), Marker { size: 14 });
+        assert_eq!(
+            Marker::from_table(&Table::new()).unwrap(),
+            Marker::default()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_invalid_values() {
+        let error = Marker::from_table(&Table::from([("length".to_string(), Value::Integer(4))]))
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "There is no setting called length, only size"
+        );
+
+        for value in [Value::Integer(0), Value::String("4".to_string())] {
+            assert!(Marker::default().set("size", &value).is_err());
+        }
+
+        assert!(integer_at_least::<u8>("size", &Value::Integer(300), 1).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::{error::Error, fmt};
+
+use crate::input::lines;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Byte range of `token` inside `text`, where `token` is a subslice of `text` (e.g. obtained
+    /// through `split`, `trim` or slicing).
+    pub fn of(text: &str, token: &str) -> Self {
+        let start = (token.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|start| start + token.len() <= text.len())
+            .expect("A span should only be taken from a subslice of the parsed text");
+
+        Self::new(start, start + token.len())
+    }
+
+    pub fn end_of(text: &str) -> Self {
+        Self::new(text.len(), text.len())
+    }
+
+    pub fn shifted(self, offset: usize) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub kind: K,
+    pub span: Span,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(kind: K, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn at(text: &str, token: &str, kind: K) -> Self {
+        Self::new(kind, Span::of(text, token))
+    }
+
+    pub fn shifted(self, offset: usize) -> Self {
+        Self::new(self.kind, self.span.shifted(offset))
+    }
+
+    pub fn within(self, text: &str, token: &str) -> Self {
+        self.shifted(Span::of(text, token).start)
+    }
+
+    pub fn locate(self, input: &str) -> LocatedError<K> {
+        let start = self.span.start.min(input.len());
+        let end = self.span.end.clamp(start, input.len());
+
+        let line_start = input[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[start..]
+            .find('\n')
+            .map_or(input.len(), |index| start + index);
+
+        LocatedError {
+            kind: self.kind,
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            snippet: input[start..end.min(line_end)].to_string(),
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            source_column: 1,
+        }
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> Error for ParseError<K> {}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct LocatedError<K> {
+    pub kind: K,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub source_line: String,
+    /// Column `source_line` starts at: 1, unless only the tail of a line too long to keep is shown.
+    pub source_column: usize,
+}
+
+impl<K: fmt::Display> fmt::Display for LocatedError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        writeln!(f, "error: {}", self.kind)?;
+        writeln!(
+            f,
+            "{}--> line {}, column {}",
+            gutter, self.line, self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line_number, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column.saturating_sub(self.source_column)),
+            "^".repeat(self.snippet.chars().count().max(1))
+        )
+    }
+}
+
+// `main` functions returning a `Result` print their error through `Debug`, so it renders the
+// same diagnostic as `Display` instead of the raw struct.
+impl<K: fmt::Display> fmt::Debug for LocatedError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n{}", self)
+    }
+}
+
+impl<K: fmt::Display> Error for LocatedError<K> {}
+
+pub fn parse_lines<T, K>(
+    input: &str,
+    mut parse_line: impl FnMut(&str) -> Result<T, ParseError<K>>,
+) -> Result<Vec<T>, LocatedError<K>> {
+    lines(input)
+        .map(|line| parse_line(line).map_err(|error| error.within(input, line).locate(input)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_subslices_by_their_position() {
+        let text = "move 1 from 2";
+
+        assert_eq!(Span::of(text, &text[5..6]), Span::new(5, 6));
+        assert_eq!(Span::end_of(text), Span::new(13, 13));
+    }
+
+    #[test]
+    fn locates_errors_by_line_and_column() {
+        let input = "123\n45x\n789";
+        let error = ParseError::new("bad digit", Span::new(6, 7)).locate(input);
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.snippet, "x");
+        assert_eq!(error.source_line, "45x");
+        assert_eq!(
+            error.to_string(),
+            "error: bad digit\n --> line 2, column 3\n  |\n2 | 45x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn shifts_line_errors_into_the_whole_input() {
+        let error = parse_lines("1\n2\nx", |line| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::at(line, line, "not a number"))
+        })
+        .unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+}
@@ -0,0 +1,3 @@
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
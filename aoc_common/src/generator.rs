@@ -0,0 +1,161 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{random::Rng, Solution};
+
+/// Builds random puzzle inputs which the day's parser accepts. `Default` should match the size
+/// of the official inputs, and `scaled` multiplies that size.
+pub trait Generator: Default {
+    fn scaled(scale: usize) -> Self;
+
+    fn generate(&self, rng: &mut Rng) -> String;
+}
+
+const NEAR_VALID_CHARACTERS: [char; 14] = [
+    ' ', '\n', '-', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'x',
+];
+
+/// Applies one or two small edits to `input` (dropping, repeating, swapping or replacing
+/// characters or lines), so that the result is almost, but not always, a valid input.
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+
+    for _ in 0..rng.range(1..=2) {
+        if chars.is_empty() {
+            chars.push(*rng.choose(&NEAR_VALID_CHARACTERS));
+            continue;
+        }
+
+        let index = rng.range(0..=chars.len() - 1);
+
+        match rng.range(0..=6) {
+            0 => {
+                chars.remove(index);
+            }
+            1 => chars.insert(index, chars[index]),
+            2 if index + 1 < chars.len() => chars.swap(index, index + 1),
+            3 => chars[index] = chars[rng.range(0..=chars.len() - 1)],
+            4 => chars[index] = *rng.choose(&NEAR_VALID_CHARACTERS),
+            5 => chars.insert(index, *rng.choose(&NEAR_VALID_CHARACTERS)),
+            _ => {
+                let line_start = chars[..index]
+                    .iter()
+                    .rposition(|&line_char| line_char == '\n')
+                    .map_or(0, |newline| newline + 1);
+                let line_end = chars[index..]
+                    .iter()
+                    .position(|&line_char| line_char == '\n')
+                    .map_or(chars.len(), |newline| index + newline + 1);
+
+                match rng.chance(0.5) {
+                    true => {
+                        chars.drain(line_start..line_end);
+                    }
+                    false => {
+                        let line = chars[line_start..line_end].to_vec();
+                        chars.splice(line_start..line_start, line);
+                    }
+                }
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+#[derive(Clone)]
+pub struct FuzzFailure {
+    pub day: u8,
+    pub iteration: usize,
+    pub input: String,
+    pub message: String,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {} panicked on mutated input #{}: {}",
+            self.day, self.iteration, self.message
+        )
+    }
+}
+
+// The failing input can be huge, so `unwrap` in tests only shows the summary. Failures are
+// reproducible from the seed, and the runner saves the input to a file.
+impl fmt::Debug for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Error for FuzzFailure {}
+
+/// The message a panic was raised with, as caught by `catch_unwind`.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("<unknown panic>".to_string(), |message| message.to_string()),
+    }
+}
+
+/// Feeds `iterations` mutated generated inputs to the parser and, when they parse, to both
+/// parts. Errors are expected; any panic is reported as a failure along with its input.
+pub fn fuzz<S: Solution>(seed: u64, iterations: usize) -> Result<(), FuzzFailure> {
+    let mut rng = Rng::new(seed);
+
+    for iteration in 0..iterations {
+        let input = mutate(&S::Generator::default().generate(&mut rng), &mut rng);
+
+        let outcome = catch_unwind(AssertUnwindSafe(|| {
+            if let Ok(parsed_input) = S::parse(&input) {
+                let _ = S::part_one(&parsed_input);
+                let _ = S::part_two(&parsed_input);
+            }
+        }));
+
+        if let Err(payload) = outcome {
+            return Err(FuzzFailure {
+                day: S::DAY,
+                iteration,
+                input,
+                message: panic_message(payload),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutations_are_small_and_reproducible() {
+        let input = "1000\n2000\n\n3000\n";
+
+        for seed in 0..100 {
+            let mutated = mutate(input, &mut Rng::new(seed));
+
+            assert_eq!(mutated, mutate(input, &mut Rng::new(seed)));
+            assert_ne!(mutated.len(), 0);
+            assert!(mutated.len().abs_diff(input.len()) <= 12);
+        }
+    }
+
+    #[test]
+    fn mutates_empty_inputs() {
+        let mutated = (0..100)
+            .map(|seed| mutate("", &mut Rng::new(seed)))
+            .collect::<Vec<_>>();
+
+        assert!(mutated.iter().any(|input| !input.is_empty()));
+    }
+}
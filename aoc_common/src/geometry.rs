@@ -0,0 +1,442 @@
+use std::{
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// An integer type points can be built from.
+pub trait Coordinate:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+pub trait SignedCoordinate: Coordinate + Neg<Output = Self> {
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => -Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_coordinate {
+    ($($integer:ty),*) => {
+        $(
+            impl Coordinate for $integer {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed_coordinate {
+    ($($integer:ty),*) => {
+        $(
+            impl SignedCoordinate for $integer {}
+        )*
+    };
+}
+
+impl_coordinate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_signed_coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// A point (or vector) on a plane, with `y` growing downwards like on a screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+macro_rules! impl_vector_ops {
+    ($point:ident { $($axis:ident),* }) => {
+        impl<T: Coordinate> $point<T> {
+            pub fn new($($axis: T),*) -> Self {
+                Self { $($axis),* }
+            }
+
+            pub fn manhattan_distance(self, other: Self) -> T {
+                T::ZERO $(+ self.$axis.abs_diff(other.$axis))*
+            }
+
+            /// The number of king moves between both points.
+            pub fn chebyshev_distance(self, other: Self) -> T {
+                T::ZERO $(.max(self.$axis.abs_diff(other.$axis)))*
+            }
+        }
+
+        impl<T: SignedCoordinate> $point<T> {
+            /// The vector with every component clamped to -1, 0 or 1.
+            pub fn signum(self) -> Self {
+                Self { $($axis: self.$axis.signum()),* }
+            }
+        }
+
+        impl<T: Coordinate> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self::Output {
+                Self { $($axis: self.$axis + other.$axis),* }
+            }
+        }
+
+        impl<T: Coordinate> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self::Output {
+                Self { $($axis: self.$axis - other.$axis),* }
+            }
+        }
+
+        impl<T: Coordinate> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self::Output {
+                Self { $($axis: self.$axis * factor),* }
+            }
+        }
+
+        impl<T: SignedCoordinate> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($axis: -self.$axis),* }
+            }
+        }
+
+        impl<T: Coordinate> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coordinate> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Point2 { x, y });
+impl_vector_ops!(Point3 { x, y, z });
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError(pub String);
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown direction: {:?}", self.0)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+/// One of the four orthogonal directions, parsed from `U/D/L/R` or `N/S/W/E`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn offset<T: SignedCoordinate>(self) -> Point2<T> {
+        match self {
+            Self::Up => Point2::new(T::ZERO, -T::ONE),
+            Self::Right => Point2::new(T::ONE, T::ZERO),
+            Self::Down => Point2::new(T::ZERO, T::ONE),
+            Self::Left => Point2::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = ParseDirectionError;
+
+    fn from_str(direction_str: &str) -> Result<Self, Self::Err> {
+        match direction_str {
+            "U" | "N" => Ok(Self::Up),
+            "R" | "E" => Ok(Self::Right),
+            "D" | "S" => Ok(Self::Down),
+            "L" | "W" => Ok(Self::Left),
+            _ => Err(ParseDirectionError(direction_str.to_string())),
+        }
+    }
+}
+
+/// One of the eight directions a king moves in, parsed from compass points
+/// (`N`, `NE`, ...) or, for the orthogonal ones, from `U/D/L/R`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn offset<T: SignedCoordinate>(self) -> Point2<T> {
+        let (up, right, down, left) = (
+            Direction4::Up.offset(),
+            Direction4::Right.offset(),
+            Direction4::Down.offset(),
+            Direction4::Left.offset(),
+        );
+
+        match self {
+            Self::Up => up,
+            Self::UpRight => up + right,
+            Self::Right => right,
+            Self::DownRight => down + right,
+            Self::Down => down,
+            Self::DownLeft => down + left,
+            Self::Left => left,
+            Self::UpLeft => up + left,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::Up => Self::Up,
+            Direction4::Right => Self::Right,
+            Direction4::Down => Self::Down,
+            Direction4::Left => Self::Left,
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(direction_str: &str) -> Result<Self, Self::Err> {
+        match direction_str {
+            "NE" => Ok(Self::UpRight),
+            "SE" => Ok(Self::DownRight),
+            "SW" => Ok(Self::DownLeft),
+            "NW" => Ok(Self::UpLeft),
+            _ => direction_str.parse::<Direction4>().map(Self::from),
+        }
+    }
+}
+
+/// The smallest rectangle holding a set of points, bounds included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    pub fn new(point: Point2<T>) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// `None` when there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+
+        let mut bounding_box = Self::new(points.next()?);
+
+        for point in points {
+            bounding_box.include(point);
+        }
+
+        Some(bounding_box)
+    }
+
+    pub fn include(&mut self, point: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_and_scales_vectors() {
+        let mut point = Point2::new(1, 2) + Point2::new(3, -4);
+
+        assert_eq!(point, Point2::new(4, -2));
+        assert_eq!(point * 3, Point2::new(12, -6));
+        assert_eq!(-point, Point2::new(-4, 2));
+
+        point -= Point2::new(4, 4);
+        assert_eq!(point, Point2::new(0, -6));
+
+        assert_eq!(
+            Point3::new(1u8, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn measures_distances() {
+        let origin = Point2::<i32>::default();
+        let point = Point2::new(-3, 4);
+
+        assert_eq!(origin.manhattan_distance(point), 7);
+        assert_eq!(origin.chebyshev_distance(point), 4);
+        assert_eq!(point.signum(), Point2::new(-1, 1));
+
+        assert_eq!(
+            Point3::new(1u32, 5, 2).manhattan_distance(Point3::new(4, 1, 2)),
+            7
+        );
+        assert_eq!(
+            Point3::new(1u32, 5, 2).chebyshev_distance(Point3::new(4, 1, 2)),
+            4
+        );
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!("U".parse(), Ok(Direction4::Up));
+        assert_eq!("E".parse(), Ok(Direction4::Right));
+        assert_eq!("NW".parse(), Ok(Direction8::UpLeft));
+        assert_eq!("L".parse(), Ok(Direction8::Left));
+        assert_eq!(
+            "NE".parse::<Direction4>(),
+            Err(ParseDirectionError("NE".to_string()))
+        );
+    }
+
+    #[test]
+    fn steps_and_turns_with_y_pointing_down() {
+        assert_eq!(Direction4::Up.offset::<i32>(), Point2::new(0, -1));
+        assert_eq!(Direction8::DownLeft.offset::<i64>(), Point2::new(-1, 1));
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Down.opposite(), Direction4::Up);
+        assert_eq!(Direction8::UpRight.opposite(), Direction8::DownLeft);
+
+        for direction in Direction8::ALL {
+            assert_eq!(
+                Point2::default().chebyshev_distance(direction.offset::<i8>()),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn bounds_points() {
+        let bounding_box =
+            BoundingBox::from_points([Point2::new(2, -1), Point2::new(-3, 4), Point2::new(0, 0)])
+                .unwrap();
+
+        assert_eq!(bounding_box.min, Point2::new(-3, -1));
+        assert_eq!(bounding_box.max, Point2::new(2, 4));
+        assert_eq!((bounding_box.width(), bounding_box.height()), (6, 6));
+        assert!(bounding_box.contains(Point2::new(2, 4)));
+        assert!(!bounding_box.contains(Point2::new(3, 0)));
+        assert_eq!(BoundingBox::<i32>::from_points([]), None);
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    fmt, iter,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::{
+    diagnostic::{ParseError, Span},
+    input::lines,
+};
+
+/// `(x, y)` coordinates, with `x` growing rightwards and `y` growing downwards.
+pub type Position = (usize, usize);
+
+/// `(dx, dy)` steps, with the same orientation as `Position`.
+pub type Offset = (isize, isize);
+
+pub const ORTHOGONAL_OFFSETS: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+pub const ALL_OFFSETS: [Offset; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseErrorKind<K> {
+    Empty,
+    RaggedRow { expected_width: usize },
+    InvalidCell(K),
+}
+
+impl<K: fmt::Display> fmt::Display for GridParseErrorKind<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The grid should have at least one cell"),
+            Self::RaggedRow { expected_width } => write!(
+                f,
+                "Every row should be as wide as the first one ({} cells)",
+                expected_width
+            ),
+            Self::InvalidCell(kind) => write!(f, "{}", kind),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "A {}x{} grid should have {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, cell: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(cell)
+            .collect();
+
+        Self::from_cells(width, height, cells)
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_cells(width, height, vec![value; width * height])
+    }
+
+    /// Parses one cell per character, one row per line. Errors point at the offending
+    /// character or row inside `text`.
+    pub fn parse<K>(
+        text: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, K>,
+    ) -> Result<Self, ParseError<GridParseErrorKind<K>>> {
+        let rows = lines(text).collect::<Vec<_>>();
+
+        let width = rows[0].chars().count();
+
+        if width == 0 {
+            return Err(ParseError::new(
+                GridParseErrorKind::Empty,
+                Span::end_of(text),
+            ));
+        }
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+
+        for row in rows.iter() {
+            if row.chars().count() != width {
+                return Err(ParseError::at(
+                    text,
+                    row,
+                    GridParseErrorKind::RaggedRow {
+                        expected_width: width,
+                    },
+                ));
+            }
+
+            for (index, cell_char) in row.char_indices() {
+                let cell = parse_cell(cell_char).map_err(|kind| {
+                    ParseError::new(
+                        GridParseErrorKind::InvalidCell(kind),
+                        Span::new(index, index + cell_char.len_utf8()),
+                    )
+                    .within(text, row)
+                })?;
+
+                cells.push(cell);
+            }
+        }
+
+        Ok(Self::from_cells(width, rows.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        match self.contains(position) {
+            true => Some(&self.cells[position.1 * self.width + position.0]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.1 * self.width + position.0]),
+            false => None,
+        }
+    }
+
+    pub fn cells(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn indexed_cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, mut transform: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_cells(
+            self.width,
+            self.height,
+            self.cells.iter().map(&mut transform).collect(),
+        )
+    }
+
+    pub fn map_indexed<U>(&self, mut transform: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid::from_fn(self.width, self.height, |position| {
+            transform(position, &self[position])
+        })
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    pub fn offset(&self, (x, y): Position, (dx, dy): Offset) -> Option<Position> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+        self.contains(position).then_some(position)
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL_OFFSETS
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_OFFSETS
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Positions met walking from `position` (excluded) by `offset` until leaving the grid.
+    pub fn ray(&self, position: Position, offset: Offset) -> impl Iterator<Item = Position> + '_ {
+        iter::successors(self.offset(position, offset), move |&position| {
+            self.offset(position, offset)
+        })
+    }
+
+    pub fn render(&self, mut render_cell: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut render_cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is outside the {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is outside the {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(text, |cell_char| cell_char.to_digit(10).ok_or(cell_char)).unwrap()
+    }
+
+    #[test]
+    fn parses_and_renders_text() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            grid.render(|&digit| if digit % 2 == 0 { '#' } else { '.' }),
+            ".#.\n#.#"
+        );
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let parse = |text| {
+            Grid::parse(text, |cell_char: char| {
+                cell_char.to_digit(10).ok_or(cell_char)
+            })
+        };
+
+        let error = parse("123\n4x6").unwrap_err();
+        assert_eq!(error.kind, GridParseErrorKind::InvalidCell('x'));
+        assert_eq!(error.span, Span::new(5, 6));
+
+        let error = parse("123\n45\n789").unwrap_err();
+        assert_eq!(
+            error.kind,
+            GridParseErrorKind::RaggedRow { expected_width: 3 }
+        );
+        assert_eq!(error.span, Span::new(4, 6));
+
+        assert_eq!(parse("\n").unwrap_err().kind, GridParseErrorKind::Empty);
+    }
+
+    #[test]
+    fn indexes_rows_and_columns() {
+        let grid = digits("123\n456");
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = digits("123\n456");
+
+        assert_eq!(grid.transpose(), digits("14\n25\n36"));
+        assert_eq!(grid.rotate_clockwise(), digits("41\n52\n63"));
+        assert_eq!(grid.rotate_counterclockwise(), digits("36\n25\n14"));
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn lists_neighbors_inside_the_grid() {
+        let grid = digits("123\n456\n789");
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn casts_rays_until_the_edge() {
+        let grid = digits("123\n456\n789");
+
+        assert_eq!(
+            grid.ray((0, 1), (1, 0)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1)]
+        );
+        assert_eq!(
+            grid.ray((2, 2), (-1, -1)).collect::<Vec<_>>(),
+            vec![(1, 1), (0, 0)]
+        );
+        assert_eq!(grid.ray((0, 0), (0, -1)).count(), 0);
+    }
+
+    #[test]
+    fn maps_cells_keeping_the_shape() {
+        let grid = digits("12\n34");
+
+        assert_eq!(grid.map(|digit| digit * 2), digits("24\n68"));
+        assert_eq!(
+            grid.map_indexed(|(x, y), _| x + y),
+            Grid::from_cells(2, 2, vec![0, 1, 1, 2])
+        );
+
+        let mut grid = Grid::filled(2, 1, false);
+        grid[(1, 0)] = true;
+
+        assert_eq!(grid.indexed_cells().filter(|(_, &lit)| lit).count(), 1);
+    }
+}
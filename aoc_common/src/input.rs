@@ -0,0 +1,159 @@
+use std::{
+    env, fmt,
+    fs::{read_to_string, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    normalize::{normalize_string, NormalizedReader},
+    Error, Result, Solution,
+};
+
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+
+const INPUT_FILE_NAME: &str = "input.txt";
+
+/// A day binary's arguments don't make sense. Shown as is, since it's meant for whoever ran it.
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// `main` shows the errors it returns with `Debug`, which would quote the message.
+impl fmt::Debug for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// The name the running binary was started with, or the one Cargo gives day `S`'s binary.
+pub fn program_name<S: Solution>() -> String {
+    env::args_os()
+        .next()
+        .as_deref()
+        .and_then(|program| Path::new(program).file_name())
+        .map_or_else(
+            || S::NAME.to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn resolve<S: Solution>(argument: Option<&str>) -> Self {
+        Self::resolve_for_directory(&S::directory(), argument)
+    }
+
+    pub fn resolve_for_directory(day_directory: &str, argument: Option<&str>) -> Self {
+        match argument {
+            Some("-") => Self::Stdin,
+            Some(path) => Self::File(PathBuf::from(path)),
+            None => {
+                let input_dir = env::var_os(INPUT_DIR_VARIABLE)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(workspace_root);
+
+                Self::File(input_dir.join(day_directory).join(INPUT_FILE_NAME))
+            }
+        }
+    }
+
+    pub fn from_args<S: Solution>() -> Result<Self> {
+        let mut args = env::args().skip(1);
+
+        let argument = args.next();
+
+        if let Some(unexpected_argument) = args.next() {
+            return Err(UsageError(format!(
+                "Unexpected argument \"{}\". Usage: {} [INPUT_PATH | -]",
+                unexpected_argument,
+                program_name::<S>()
+            ))
+            .into());
+        }
+
+        Ok(Self::resolve::<S>(argument.as_deref()))
+    }
+
+    /// Reads the whole input, normalized so that it parses the same whichever editor saved it.
+    pub fn read(&self) -> Result<String> {
+        let input = match self {
+            Self::Stdin => {
+                let mut input = String::new();
+
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|error| format!("Could not read input from stdin: {}", error))?;
+
+                input
+            }
+            Self::File(path) => read_input_from(path)?,
+        };
+
+        Ok(normalize_string(input))
+    }
+
+    /// Opens the input for reading bit by bit instead of loading it whole, normalized like `read`
+    /// does.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Self::Stdin => Ok(Box::new(NormalizedReader::new(io::stdin().lock()))),
+            Self::File(path) => {
+                let file = File::open(path).map_err(|error| read_error(path, error))?;
+
+                Ok(Box::new(NormalizedReader::new(BufReader::new(file))))
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+pub fn read_input_from(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+
+    read_to_string(path).map_err(|error| read_error(path, error))
+}
+
+fn read_error(path: &Path, error: io::Error) -> Error {
+    format!(
+        "Could not read input file {}: {} (pass an input path, - for stdin, or set {})",
+        path.display(),
+        error,
+        INPUT_DIR_VARIABLE
+    )
+    .into()
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.trim().split('\n')
+}
+
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    input.trim().split("\n\n")
+}
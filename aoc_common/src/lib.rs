@@ -0,0 +1,20 @@
+pub mod answer;
+pub mod binary;
+pub mod cache;
+pub mod config;
+pub mod diagnostic;
+pub mod error;
+pub mod generator;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod normalize;
+pub mod random;
+pub mod solution;
+pub mod stream;
+pub mod toml;
+pub mod top;
+pub mod variants;
+
+pub use error::{Error, Result};
+pub use solution::Solution;
@@ -0,0 +1,239 @@
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, Read},
+};
+
+const BYTE_ORDER_MARK: &str = "\u{feff}";
+
+fn is_trailing_whitespace(byte: u8) -> bool {
+    byte != b'\n' && byte.is_ascii_whitespace()
+}
+
+/// Gets rid of what editors add to inputs without changing their meaning: a leading UTF-8 byte
+/// order mark, `\r\n` line endings and whitespace at the end of lines. Parsers can then split on
+/// `'\n'` and `"\n\n"` alone. Lines are never added or removed, so line numbers in errors still
+/// match the original input.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+
+    let is_normalized = input
+        .split('\n')
+        .all(|line| !line.bytes().last().is_some_and(is_trailing_whitespace));
+
+    match is_normalized {
+        true => Cow::Borrowed(input),
+        false => Cow::Owned(
+            input
+                .split('\n')
+                .map(|line| {
+                    line.trim_end_matches(|line_char: char| line_char.is_ascii_whitespace())
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+/// Like `normalize`, but reuses `input`'s allocation when at most a byte order mark is dropped.
+pub fn normalize_string(mut input: String) -> String {
+    let normalized_length = match normalize(&input) {
+        Cow::Owned(normalized) => return normalized,
+        Cow::Borrowed(normalized) => normalized.len(),
+    };
+
+    input.drain(..input.len() - normalized_length);
+
+    input
+}
+
+/// Streaming counterpart of `normalize`. It only holds back runs of whitespace until it knows
+/// whether they end their line, so single-line inputs of any size still stream.
+pub struct NormalizedReader<R> {
+    reader: R,
+    normalizer: Normalizer,
+    position: usize,
+}
+
+struct Normalizer {
+    output: Vec<u8>,
+    pending_whitespace: Vec<u8>,
+    /// How much of the byte order mark the input started with, until a byte tells it apart.
+    byte_order_mark_matched: Option<usize>,
+}
+
+impl Normalizer {
+    fn push(&mut self, byte: u8) {
+        if let Some(matched) = self.byte_order_mark_matched {
+            let byte_order_mark = BYTE_ORDER_MARK.as_bytes();
+
+            if byte == byte_order_mark[matched] {
+                self.byte_order_mark_matched =
+                    Some(matched + 1).filter(|&matched| matched < byte_order_mark.len());
+
+                return;
+            }
+
+            self.output.extend_from_slice(&byte_order_mark[..matched]);
+            self.byte_order_mark_matched = None;
+        }
+
+        match byte {
+            b'\n' => {
+                self.pending_whitespace.clear();
+                self.output.push(byte);
+            }
+            _ if is_trailing_whitespace(byte) => self.pending_whitespace.push(byte),
+            _ => {
+                self.output.append(&mut self.pending_whitespace);
+                self.output.push(byte);
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        // A partial byte order mark is just text, while whitespace ending the input ends its
+        // last line.
+        if let Some(matched) = self.byte_order_mark_matched.take() {
+            self.output
+                .extend_from_slice(&BYTE_ORDER_MARK.as_bytes()[..matched]);
+        }
+
+        self.pending_whitespace.clear();
+    }
+}
+
+impl<R: BufRead> NormalizedReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            normalizer: Normalizer {
+                output: Vec::new(),
+                pending_whitespace: Vec::new(),
+                byte_order_mark_matched: Some(0),
+            },
+            position: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for NormalizedReader<R> {
+    fn read(&mut self, output: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(output.len());
+
+        output[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Ok(length)
+    }
+}
+
+impl<R: BufRead> BufRead for NormalizedReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.position == self.normalizer.output.len() {
+            self.normalizer.output.clear();
+            self.position = 0;
+
+            let chunk = self.reader.fill_buf()?;
+            let chunk_length = chunk.len();
+
+            if chunk_length == 0 {
+                self.normalizer.finish();
+                break;
+            }
+
+            for &byte in chunk {
+                self.normalizer.push(byte);
+            }
+
+            self.reader.consume(chunk_length);
+        }
+
+        Ok(&self.normalizer.output[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.normalizer.output.len());
+    }
+}
+
+/// The same input as saved by different editors: as is, with `\r\n` line endings, with a byte
+/// order mark, and with all of them plus trailing whitespace. Every day should solve them alike
+/// once normalized.
+pub fn variants(input: &str) -> [(&'static str, String); 4] {
+    [
+        ("LF", input.to_string()),
+        ("CRLF", input.replace('\n', "\r\n")),
+        ("BOM", format!("{}{}", BYTE_ORDER_MARK, input)),
+        (
+            "BOM, CRLF and trailing whitespace",
+            format!("{}{}", BYTE_ORDER_MARK, input.replace('\n', " \t\r\n")),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_normalized(input: &[u8]) -> Vec<u8> {
+        let mut output = Vec::new();
+
+        NormalizedReader::new(input)
+            .read_to_end(&mut output)
+            .unwrap();
+
+        output
+    }
+
+    #[test]
+    fn drops_byte_order_marks_carriage_returns_and_trailing_whitespace() {
+        assert_eq!(normalize("\u{feff}a\r\n\r\nb  \n c\t\r\n"), "a\n\nb\n c\n");
+        assert_eq!(normalize("a \nb"), "a\nb");
+        assert_eq!(normalize("x\u{feff}"), "x\u{feff}");
+    }
+
+    #[test]
+    fn borrows_inputs_with_nothing_to_change() {
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb\n")));
+        assert!(matches!(normalize("\u{feff}a\n"), Cow::Borrowed("a\n")));
+        assert_eq!(normalize_string("\u{feff}a\n".to_string()), "a\n");
+        assert_eq!(normalize_string("a\r\n".to_string()), "a\n");
+    }
+
+    #[test]
+    fn normalizes_every_variant_to_the_original() {
+        let input = "    [D]\n 1   2\n\nmove 1 from 2 to 1\n";
+
+        for (variant, text) in variants(input) {
+            assert_eq!(normalize(&text), input, "{}", variant);
+            assert_eq!(
+                read_normalized(text.as_bytes()),
+                input.as_bytes(),
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn streams_like_it_normalizes_across_chunks() {
+        for input in [
+            "\u{feff}ab \r\n\r\n  c\t\r\n",
+            "\u{fec0}x",
+            "\u{feff}",
+            "a \t ",
+            "ab",
+        ] {
+            // One byte at a time, so that every run of whitespace and the byte order mark are
+            // split between reads.
+            let mut reader =
+                NormalizedReader::new(io::BufReader::with_capacity(1, input.as_bytes()));
+            let mut output = String::new();
+
+            reader.read_to_string(&mut output).unwrap();
+
+            assert_eq!(output, normalize(input), "{:?}", input);
+        }
+    }
+}
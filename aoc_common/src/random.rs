@@ -0,0 +1,125 @@
+use std::ops::RangeInclusive;
+
+/// SplitMix64, which is small, fast and good enough to generate puzzle inputs from a seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u128) -> u128 {
+        (self.next_u64() as u128 * bound) >> 64
+    }
+
+    pub fn range<T: Sample>(&mut self, range: RangeInclusive<T>) -> T {
+        T::sample(self, range)
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.range(0..=values.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for index in (1..values.len()).rev() {
+            values.swap(index, self.range(0..=index));
+        }
+    }
+}
+
+pub trait Sample: Copy {
+    fn sample(rng: &mut Rng, range: RangeInclusive<Self>) -> Self;
+}
+
+macro_rules! impl_sample {
+    ($($integer:ty),*) => {
+        $(
+            impl Sample for $integer {
+                fn sample(rng: &mut Rng, range: RangeInclusive<Self>) -> Self {
+                    let (start, end) = range.into_inner();
+
+                    assert!(start <= end, "Can't sample from the empty range {}..={}", start, end);
+
+                    let width = (end as i128 - start as i128) as u128 + 1;
+
+                    (start as i128 + rng.below(width) as i128) as $integer
+                }
+            }
+        )*
+    };
+}
+
+impl_sample!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_reproducible_from_the_seed() {
+        let mut first = Rng::new(2022);
+        let mut second = Rng::new(2022);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn samples_within_inclusive_ranges() {
+        let mut rng = Rng::new(0);
+
+        let samples = (0..1000).map(|_| rng.range(-2..=2)).collect::<Vec<i32>>();
+
+        assert!(samples.iter().all(|sample| (-2..=2).contains(sample)));
+        assert!((-2..=2).all(|value| samples.contains(&value)));
+        assert_eq!(rng.range(u64::MAX..=u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn samples_full_width_ranges() {
+        let mut rng = Rng::new(0);
+
+        let samples = (0..5000)
+            .map(|_| rng.range(i8::MIN..=i8::MAX))
+            .collect::<Vec<_>>();
+
+        assert!(samples.contains(&i8::MIN));
+        assert!(samples.contains(&i8::MAX));
+        assert_ne!(
+            rng.range(i64::MIN..=i64::MAX),
+            rng.range(i64::MIN..=i64::MAX)
+        );
+    }
+
+    #[test]
+    fn shuffles_without_losing_values() {
+        let mut values = (0..50).collect::<Vec<_>>();
+
+        Rng::new(7).shuffle(&mut values);
+
+        assert_ne!(values, (0..50).collect::<Vec<_>>());
+
+        values.sort_unstable();
+
+        assert_eq!(values, (0..50).collect::<Vec<_>>());
+    }
+}
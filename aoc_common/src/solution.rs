@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use crate::{answer::Answer, config::Config, generator::Generator, Result};
+
+pub trait Solution {
+    const DAY: u8;
+    const NAME: &'static str;
+
+    type Input;
+    type PartOne: Display + Into<Answer>;
+    type PartTwo: Display + Into<Answer>;
+
+    type Generator: Generator;
+
+    type Config: Config;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one_with(input: &Self::Input, config: &Self::Config) -> Result<Self::PartOne>;
+
+    fn part_two_with(input: &Self::Input, config: &Self::Config) -> Result<Self::PartTwo>;
+
+    /// Solves the first part with the puzzle's own constants.
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        Self::part_one_with(input, &Self::Config::default())
+    }
+
+    /// Solves the second part with the puzzle's own constants.
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        Self::part_two_with(input, &Self::Config::default())
+    }
+
+    fn directory() -> String {
+        format!("{:02}_{}", Self::DAY, Self::NAME)
+    }
+}
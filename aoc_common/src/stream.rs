@@ -0,0 +1,155 @@
+use std::{fmt, io::BufRead};
+
+use crate::{
+    diagnostic::{LocatedError, ParseError},
+    Result, Solution,
+};
+
+/// A solution able to go through its input in a single pass, keeping only a summary of what both
+/// parts need instead of the whole parsed input.
+pub trait StreamingSolution: Solution {
+    type Summary;
+
+    fn summarize_stream(reader: &mut dyn BufRead, config: &Self::Config) -> Result<Self::Summary>;
+
+    fn part_one_from_summary(
+        summary: &Self::Summary,
+        config: &Self::Config,
+    ) -> Result<Self::PartOne>;
+
+    fn part_two_from_summary(
+        summary: &Self::Summary,
+        config: &Self::Config,
+    ) -> Result<Self::PartTwo>;
+}
+
+/// Reads a stream line by line into a single reused buffer.
+pub struct LineReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// The next line, without its line ending, along with its 1-based number.
+    pub fn next_line(&mut self) -> Result<Option<(usize, &str)>> {
+        self.line.clear();
+
+        let read = self
+            .reader
+            .read_line(&mut self.line)
+            .map_err(|error| format!("Could not read line {}: {}", self.line_number + 1, error))?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        self.line_number += 1;
+
+        Ok(Some((
+            self.line_number,
+            self.line.strip_suffix('\n').unwrap_or(&self.line),
+        )))
+    }
+}
+
+/// Locates an error on `line`, which is line `line_number` of a stream.
+pub fn locate_on_line<K>(error: ParseError<K>, line: &str, line_number: usize) -> LocatedError<K> {
+    LocatedError {
+        line: line_number,
+        ..error.locate(line)
+    }
+}
+
+/// Streaming counterpart of `diagnostic::parse_lines`, handing every parsed line to `consume`.
+/// Blank lines are only accepted at both ends of the input, like `parse_lines` does.
+pub fn parse_stream_lines<T, K>(
+    reader: &mut dyn BufRead,
+    mut parse_line: impl FnMut(&str) -> Result<T, ParseError<K>>,
+    mut consume: impl FnMut(T),
+) -> Result<()>
+where
+    K: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    let mut lines = LineReader::new(reader);
+
+    let mut has_records = false;
+    let mut first_blank_line_number = None;
+
+    while let Some((line_number, line)) = lines.next_line()? {
+        if line.trim().is_empty() {
+            if has_records {
+                first_blank_line_number.get_or_insert(line_number);
+            }
+
+            continue;
+        }
+
+        if let Some(blank_line_number) = first_blank_line_number.take() {
+            parse_line("").map_err(|error| locate_on_line(error, "", blank_line_number))?;
+        }
+
+        has_records = true;
+
+        consume(parse_line(line).map_err(|error| locate_on_line(error, line, line_number))?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostic::Span;
+
+    use super::*;
+
+    #[test]
+    fn reads_lines_without_their_endings() {
+        let mut lines = LineReader::new("first\nsecond\n\nlast".as_bytes());
+
+        assert_eq!(lines.next_line().unwrap(), Some((1, "first")));
+        assert_eq!(lines.next_line().unwrap(), Some((2, "second")));
+        assert_eq!(lines.next_line().unwrap(), Some((3, "")));
+        assert_eq!(lines.next_line().unwrap(), Some((4, "last")));
+        assert_eq!(lines.next_line().unwrap(), None);
+    }
+
+    #[test]
+    fn locates_errors_on_stream_lines() {
+        let error = locate_on_line(ParseError::new("bad", Span::new(2, 3)), "12x", 7);
+
+        assert_eq!((error.line, error.column), (7, 3));
+        assert_eq!(error.source_line, "12x");
+    }
+
+    #[test]
+    fn parses_stream_lines_like_whole_inputs() {
+        let parse = |line: &str| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::at(line, line, "not a number"))
+        };
+
+        let mut numbers = vec![];
+        parse_stream_lines(&mut "\n1\n2\n\n".as_bytes(), parse, |number| {
+            numbers.push(number)
+        })
+        .unwrap();
+
+        assert_eq!(numbers, vec![1, 2]);
+
+        let error = parse_stream_lines(&mut "1\n\n2".as_bytes(), parse, |_| {})
+            .unwrap_err()
+            .downcast::<LocatedError<&str>>()
+            .unwrap();
+
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
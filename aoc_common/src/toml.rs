@@ -0,0 +1,360 @@
+use std::{collections::BTreeMap, fmt, fs::read_to_string, path::Path};
+
+use crate::{
+    diagnostic::{self, LocatedError, Span},
+    Result,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Float(value) if value.fract() == 0.0 => write!(f, "{:.1}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Boolean(value) => write!(f, "{}", value),
+            Self::String(value) if value.contains('\n') => {
+                write!(f, "\"\"\"\n{}\"\"\"", escape(value, false))
+            }
+            Self::String(value) => write!(f, "\"{}\"", escape(value, true)),
+        }
+    }
+}
+
+fn escape(value: &str, escape_newlines: bool) -> String {
+    value
+        .chars()
+        .map(|value_char| match value_char {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\t' => "\\t".to_string(),
+            '\n' if escape_newlines => "\\n".to_string(),
+            _ => value_char.to_string(),
+        })
+        .collect()
+}
+
+pub type Table = BTreeMap<String, Value>;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    pub tables: BTreeMap<String, Table>,
+}
+
+impl Document {
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    pub fn get(&self, table: &str, key: &str) -> Option<&Value> {
+        self.table(table).and_then(|table| table.get(key))
+    }
+
+    pub fn insert(&mut self, table: &str, key: &str, value: Value) {
+        self.tables
+            .entry(table.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let contents = read_to_string(path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+
+        parse(&contents).map_err(|error| format!("In {}:\n{}", path.display(), error).into())
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first_table = true;
+
+        for (name, table) in self.tables.iter() {
+            if !name.is_empty() {
+                if !first_table {
+                    writeln!(f)?;
+                }
+
+                writeln!(f, "[{}]", name)?;
+            }
+
+            for (key, value) in table.iter() {
+                writeln!(f, "{} = {}", key, value)?;
+            }
+
+            first_table = false;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MalformedTableHeader,
+    MissingEquals,
+    InvalidKey,
+    InvalidValue,
+    UnterminatedString,
+    DuplicateKey,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedTableHeader => write!(f, "A table header should look like [name]"),
+            Self::MissingEquals => write!(f, "A key should be followed by = and a value"),
+            Self::InvalidKey => write!(
+                f,
+                "A key should only contain letters, digits, underscores and dashes"
+            ),
+            Self::InvalidValue => write!(
+                f,
+                "A value should be an integer, a float, a boolean or a quoted string"
+            ),
+            Self::UnterminatedString => write!(f, "This string is never closed"),
+            Self::DuplicateKey => write!(f, "This key was already defined in the same table"),
+        }
+    }
+}
+
+pub type ParseError = diagnostic::ParseError<ParseErrorKind>;
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|key_char| key_char.is_ascii_alphanumeric() || matches!(key_char, '_' | '-'))
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, line_char) in line.char_indices() {
+        match line_char {
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+
+        escaped = line_char == '\\' && !escaped;
+    }
+
+    line
+}
+
+fn unescape(text: &str, raw: &str) -> Result<String, ParseError> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(raw_char) = chars.next() {
+        if raw_char != '\\' {
+            value.push(raw_char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('"') => value.push('"'),
+            Some('\\') => value.push('\\'),
+            _ => return Err(ParseError::at(text, raw, ParseErrorKind::InvalidValue)),
+        }
+    }
+
+    Ok(value)
+}
+
+fn parse_scalar(text: &str, value_str: &str) -> Result<Value, ParseError> {
+    if let Some(quoted) = value_str.strip_prefix('"') {
+        return match quoted.strip_suffix('"') {
+            Some(raw) if !raw.ends_with('\\') || raw.ends_with("\\\\") => {
+                Ok(Value::String(unescape(text, raw)?))
+            }
+            _ => Err(ParseError::at(
+                text,
+                value_str,
+                ParseErrorKind::UnterminatedString,
+            )),
+        };
+    }
+
+    let number_str = value_str.replace('_', "");
+
+    match value_str {
+        "true" => Ok(Value::Boolean(true)),
+        "false" => Ok(Value::Boolean(false)),
+        _ => number_str
+            .parse::<i128>()
+            .map(Value::Integer)
+            .or_else(|_| number_str.parse::<f64>().map(Value::Float))
+            .map_err(|_| ParseError::at(text, value_str, ParseErrorKind::InvalidValue)),
+    }
+}
+
+pub fn parse(text: &str) -> Result<Document, LocatedError<ParseErrorKind>> {
+    parse_document(text).map_err(|error| error.locate(text))
+}
+
+/// Parses a lone value, as written after a key's `=`.
+pub fn parse_value(text: &str) -> Result<Value, LocatedError<ParseErrorKind>> {
+    parse_scalar(text, text.trim()).map_err(|error| error.locate(text))
+}
+
+fn parse_document(text: &str) -> Result<Document, ParseError> {
+    let mut document = Document::default();
+    let mut current_table = String::new();
+    let mut remaining = text;
+
+    while !remaining.is_empty() {
+        let (raw_line, rest) = remaining.split_once('\n').unwrap_or((remaining, ""));
+        remaining = rest;
+
+        let line = strip_comment(raw_line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| name.split('.').all(is_valid_key))
+                .ok_or_else(|| ParseError::at(text, line, ParseErrorKind::MalformedTableHeader))?;
+
+            current_table = name.to_string();
+            document.tables.entry(current_table.clone()).or_default();
+
+            continue;
+        }
+
+        let (key, value_str) = line
+            .split_once('=')
+            .ok_or_else(|| ParseError::at(text, line, ParseErrorKind::MissingEquals))?;
+
+        let key = key.trim();
+        let value_str = value_str.trim();
+
+        if !is_valid_key(key) {
+            return Err(ParseError::at(text, key, ParseErrorKind::InvalidKey));
+        }
+
+        let value = match value_str.strip_prefix("\"\"\"") {
+            Some(opening_rest) => {
+                let string_start = value_str.len() - opening_rest.len();
+                let value_offset = Span::of(text, value_str).start + string_start;
+                let after_opening = &text[value_offset..];
+                let after_opening = after_opening.strip_prefix('\n').unwrap_or(after_opening);
+
+                let closing_index = after_opening.find("\"\"\"").ok_or_else(|| {
+                    ParseError::at(text, value_str, ParseErrorKind::UnterminatedString)
+                })?;
+
+                let raw = &after_opening[..closing_index];
+                let after_closing = &after_opening[closing_index + 3..];
+
+                remaining = after_closing.split_once('\n').map_or("", |(_, rest)| rest);
+
+                Value::String(unescape(text, raw)?)
+            }
+            None => parse_scalar(text, value_str)?,
+        };
+
+        let table = document.tables.entry(current_table.clone()).or_default();
+
+        if table.insert(key.to_string(), value).is_some() {
+            return Err(ParseError::at(text, key, ParseErrorKind::DuplicateKey));
+        }
+    }
+
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_keys_and_values() {
+        let document = parse(
+            "title = \"answers\" # comment\n\n[day01]\npart1 = 24_000\nratio = 0.5\nok = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            document.get("", "title"),
+            Some(&Value::String("answers".to_string()))
+        );
+        assert_eq!(document.get("day01", "part1"), Some(&Value::Integer(24000)));
+        assert_eq!(document.get("day01", "ratio"), Some(&Value::Float(0.5)));
+        assert_eq!(document.get("day01", "ok"), Some(&Value::Boolean(true)));
+        assert_eq!(
+            parse_value(" 70_000_000").unwrap(),
+            Value::Integer(70000000)
+        );
+    }
+
+    #[test]
+    fn parses_escapes_and_multi_line_strings() {
+        let document = parse("a = \"x # \\\"y\\\"\"\nb = \"\"\"\n#.\n.#\"\"\"\nc = 1").unwrap();
+
+        assert_eq!(
+            document.get("", "a"),
+            Some(&Value::String("x # \"y\"".to_string()))
+        );
+        assert_eq!(
+            document.get("", "b"),
+            Some(&Value::String("#.\n.#".to_string()))
+        );
+        assert_eq!(document.get("", "c"), Some(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn round_trips_documents() {
+        let mut document = Document::default();
+
+        document.insert("day05", "part1", Value::String("CMZ".to_string()));
+        document.insert("day10", "part2", Value::String("##\n..".to_string()));
+        document.insert("day10", "part1", Value::Integer(-13140));
+
+        assert_eq!(parse(&document.to_string()).unwrap(), document);
+    }
+
+    #[test]
+    fn reports_located_errors() {
+        let error = parse("[day01]\npart1 = 1\npart1 = 2").unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::DuplicateKey);
+        assert_eq!((error.line, error.column), (3, 1));
+
+        assert_eq!(
+            parse("a = \"open").unwrap_err().kind,
+            ParseErrorKind::UnterminatedString
+        );
+        assert_eq!(
+            parse("a = x").unwrap_err().kind,
+            ParseErrorKind::InvalidValue
+        );
+        assert_eq!(parse("a").unwrap_err().kind, ParseErrorKind::MissingEquals);
+        assert_eq!(
+            parse_value("4x").unwrap_err().kind,
+            ParseErrorKind::InvalidValue
+        );
+        assert_eq!(
+            parse("[day 1]").unwrap_err().kind,
+            ParseErrorKind::MalformedTableHeader
+        );
+    }
+}
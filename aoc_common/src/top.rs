@@ -0,0 +1,119 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Ranked<T> {
+    item: T,
+    index: usize,
+}
+
+impl<T: Ord> Ord for Ranked<T> {
+    /// Equal items rank by index, the earliest first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.item
+            .cmp(&other.item)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<T: Ord> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Keeps the `k` largest items pushed into it, along with the index each one was pushed at, in
+/// O(log k) time per item and O(k) memory. Among equal items, the earliest ones are kept.
+#[derive(Debug)]
+pub struct TopK<T> {
+    k: usize,
+    pushed: usize,
+    /// A min-heap, so that the smallest kept item is the one at hand to replace.
+    heap: BinaryHeap<Reverse<Ranked<T>>>,
+}
+
+impl<T: Ord> TopK<T> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            pushed: 0,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let ranked = Ranked {
+            item,
+            index: self.pushed,
+        };
+
+        self.pushed += 1;
+
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(ranked));
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if ranked > smallest.0 {
+                *smallest = Reverse(ranked);
+            }
+        }
+    }
+
+    /// The kept items with their indices, largest first. There are fewer than `k` of them when
+    /// fewer were pushed.
+    pub fn into_sorted_vec(self) -> Vec<(usize, T)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| (ranked.index, ranked.item))
+            .collect()
+    }
+}
+
+/// The `k` largest `items` with their indices, largest first.
+pub fn top_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<(usize, T)> {
+    let mut top = TopK::new(k);
+
+    for item in items {
+        top.push(item);
+    }
+
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Rng;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_largest_items_with_their_indices() {
+        assert_eq!(
+            top_k([6, 4, 11, 24, 10], 3),
+            vec![(3, 24), (2, 11), (4, 10)]
+        );
+        assert_eq!(top_k([6, 4], 3), vec![(0, 6), (1, 4)]);
+        assert_eq!(top_k([6, 4], 0), vec![]);
+        assert_eq!(top_k(Vec::<u32>::new(), 3), vec![]);
+    }
+
+    #[test]
+    fn keeps_the_earliest_of_equal_items() {
+        assert_eq!(top_k([5, 7, 5, 7, 5], 3), vec![(1, 7), (3, 7), (0, 5)]);
+    }
+
+    #[test]
+    fn ranks_like_a_full_sort() {
+        let mut rng = Rng::new(7);
+        let items = (0..1000).map(|_| rng.range(0..=50u32)).collect::<Vec<_>>();
+
+        let mut sorted = items.iter().copied().enumerate().collect::<Vec<_>>();
+        sorted.sort_by_key(|&(index, item)| (Reverse(item), index));
+
+        for k in [1, 10, 999, 1000, 2000] {
+            assert_eq!(top_k(items.iter().copied(), k), sorted[..k.min(1000)]);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    answer::{Answer, Part},
+    Result, Solution,
+};
+
+/// An algorithm solving a part of `S`, named after how it does it.
+pub struct Solver<S: Solution, T> {
+    pub name: &'static str,
+    pub solve: fn(&S::Input, &S::Config) -> Result<T>,
+}
+
+/// A solution with more than one algorithm for some of its parts, so that they can be timed
+/// against each other. The first solver of each part is the reference the others are checked
+/// against, which should be the solution's own `part_one_with` or `part_two_with`: the readable
+/// one, rather than the fast one.
+pub trait SolutionVariants: Solution + Sized {
+    fn part_one_solvers() -> Vec<Solver<Self, Self::PartOne>> {
+        vec![Solver {
+            name: "reference",
+            solve: Self::part_one_with,
+        }]
+    }
+
+    fn part_two_solvers() -> Vec<Solver<Self, Self::PartTwo>> {
+        vec![Solver {
+            name: "reference",
+            solve: Self::part_two_with,
+        }]
+    }
+}
+
+/// Whether a solver's outcome matches the reference's. Failing is an outcome too: a solver which
+/// fails where the reference does agrees with it, whatever its error says.
+pub fn agree(reference: &Result<Answer>, other: &Result<Answer>) -> bool {
+    match (reference, other) {
+        (Ok(reference), Ok(other)) => reference == other,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+fn describe(outcome: &Result<Answer>) -> String {
+    match outcome {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("failed ({})", error),
+    }
+}
+
+fn cross_check_part<S: Solution, T: Into<Answer>>(
+    part: Part,
+    solvers: Vec<Solver<S, T>>,
+    input: &S::Input,
+    config: &S::Config,
+) -> Result<()> {
+    let mut solvers = solvers.into_iter();
+
+    let reference = solvers
+        .next()
+        .ok_or_else(|| format!("Part {} of day {} has no solver", part, S::DAY))?;
+    let expected = (reference.solve)(input, config).map(Into::into);
+
+    for solver in solvers {
+        let outcome = (solver.solve)(input, config).map(Into::into);
+
+        if !agree(&expected, &outcome) {
+            return Err(format!(
+                "Part {} of day {}: {} answered {}, but {} answered {}",
+                part,
+                S::DAY,
+                solver.name,
+                describe(&outcome),
+                reference.name,
+                describe(&expected)
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs every solver of both parts on `input`, failing on the first one which disagrees with its
+/// reference.
+pub fn cross_check<S: SolutionVariants>(input: &S::Input, config: &S::Config) -> Result<()> {
+    cross_check_part(Part::One, S::part_one_solvers(), input, config)?;
+    cross_check_part(Part::Two, S::part_two_solvers(), input, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_on_equal_answers_and_on_failures() {
+        assert!(agree(&Ok(Answer::Integer(7)), &Ok(Answer::Integer(7))));
+        assert!(agree(&Err("no marker".into()), &Err("no window".into())));
+        assert!(!agree(&Ok(Answer::Integer(7)), &Ok(Answer::Integer(8))));
+        assert!(!agree(&Ok(Answer::Integer(7)), &Err("no marker".into())));
+        assert!(!agree(&Err("no marker".into()), &Ok(Answer::Integer(7))));
+    }
+}